@@ -1604,11 +1604,13 @@ pub fn visit_lit_char_mut<V: VisitMut + ?Sized>(_visitor: &mut V, _i: &mut LitCh
 # [ cfg ( any ( feature = "full" , feature = "derive" ) ) ]
 pub fn visit_lit_float_mut<V: VisitMut + ?Sized>(_visitor: &mut V, _i: &mut LitFloat) {
     // Skipped field _i . token;
+    // Skipped field _i . repr;
     _visitor.visit_span_mut(& mut _i . span);
 }
 # [ cfg ( any ( feature = "full" , feature = "derive" ) ) ]
 pub fn visit_lit_int_mut<V: VisitMut + ?Sized>(_visitor: &mut V, _i: &mut LitInt) {
     // Skipped field _i . token;
+    // Skipped field _i . repr;
     _visitor.visit_span_mut(& mut _i . span);
 }
 # [ cfg ( any ( feature = "full" , feature = "derive" ) ) ]
@@ -227,6 +227,23 @@ impl Hash for Ident {
     }
 }
 
+/// Whether `s` is a Rust keyword, reserved or in use, which the `Ident`
+/// parser refuses to accept as an identifier.
+#[cfg(any(feature = "parsing", feature = "full", feature = "derive"))]
+pub fn is_keyword(s: &str) -> bool {
+    match s {
+        // From https://doc.rust-lang.org/grammar.html#keywords
+        "abstract" | "alignof" | "as" | "become" | "box" | "break" | "const" | "continue"
+        | "crate" | "do" | "else" | "enum" | "extern" | "false" | "final" | "fn" | "for" | "if"
+        | "impl" | "in" | "let" | "loop" | "macro" | "match" | "mod" | "move" | "mut"
+        | "offsetof" | "override" | "priv" | "proc" | "pub" | "pure" | "ref" | "return"
+        | "Self" | "self" | "sizeof" | "static" | "struct" | "super" | "trait" | "true"
+        | "type" | "typeof" | "unsafe" | "unsized" | "use" | "virtual" | "where" | "while"
+        | "yield" => true,
+        _ => false,
+    }
+}
+
 #[cfg(feature = "parsing")]
 pub mod parsing {
     use super::*;
@@ -244,16 +261,8 @@ pub mod parsing {
             if term.as_str().starts_with('\'') {
                 return parse_error();
             }
-            match term.as_str() {
-                // From https://doc.rust-lang.org/grammar.html#keywords
-                "abstract" | "alignof" | "as" | "become" | "box" | "break" | "const"
-                | "continue" | "crate" | "do" | "else" | "enum" | "extern" | "false" | "final"
-                | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "macro" | "match"
-                | "mod" | "move" | "mut" | "offsetof" | "override" | "priv" | "proc" | "pub"
-                | "pure" | "ref" | "return" | "Self" | "self" | "sizeof" | "static" | "struct"
-                | "super" | "trait" | "true" | "type" | "typeof" | "unsafe" | "unsized" | "use"
-                | "virtual" | "where" | "while" | "yield" => return parse_error(),
-                _ => {}
+            if is_keyword(term.as_str()) {
+                return parse_error();
             }
 
             Ok((
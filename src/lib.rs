@@ -254,6 +254,9 @@
 //!   types.
 //! - **`extra-traits`** — Debug, Eq, PartialEq, Hash impls for all syntax tree
 //!   types.
+//! - **`lit-encoding`** — Compact binary encoding and decoding of literals.
+//! - **`i128`** — Literal APIs that take or return `u128` and `i128` values.
+//!   These types need Rust 1.26 or newer.
 
 // Syn types in rustdoc of other crates get linked to here.
 #![doc(html_root_url = "https://docs.rs/syn/0.12.5")]
@@ -344,8 +347,9 @@ pub use lifetime::Lifetime;
 #[cfg(any(feature = "full", feature = "derive"))]
 mod lit;
 #[cfg(any(feature = "full", feature = "derive"))]
-pub use lit::{FloatSuffix, IntSuffix, Lit, LitBool, LitByte, LitByteStr, LitChar, LitFloat,
-              LitInt, LitStr, LitVerbatim, StrStyle};
+pub use lit::{FloatSuffix, IntSuffix, Lit, LitBool, LitByte, LitByteStr, LitByteStrBytes, LitChar,
+              LitError, LitFloat, LitInt, LitKey, LitKind, LitStr, LitVerbatim, Scalar, SignedLit,
+              StrStyle};
 
 #[cfg(any(feature = "full", feature = "derive"))]
 mod mac;
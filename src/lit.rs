@@ -6,11 +6,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use proc_macro2::{Literal, Span, TokenNode};
+use proc_macro2::{Literal, Span, Term, TokenNode, TokenStream, TokenTree};
+use std::ascii;
+use std::cmp::Ordering;
+use std::collections::hash_map::{Entry, HashMap};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::num::FpCategory;
 use std::str;
+use unicode_xid::UnicodeXID;
 
-#[cfg(feature = "printing")]
-use proc_macro2::{Term, TokenTree};
+use ident;
 
 #[cfg(feature = "extra-traits")]
 use std::hash::{Hash, Hasher};
@@ -54,185 +60,2321 @@ ast_enum_of_structs! {
             pub span: Span,
         }),
 
-        /// A character literal: `'a'`.
-        ///
-        /// *This type is available if Syn is built with the `"derive"` or
-        /// `"full"` feature.*
-        pub Char(LitChar #manual_extra_traits {
-            token: Literal,
-            pub span: Span,
-        }),
+        /// A character literal: `'a'`.
+        ///
+        /// *This type is available if Syn is built with the `"derive"` or
+        /// `"full"` feature.*
+        pub Char(LitChar #manual_extra_traits {
+            token: Literal,
+            pub span: Span,
+        }),
+
+        /// An integer literal: `1` or `1u16`.
+        ///
+        /// Holds up to 64 bits of data. Use `LitVerbatim` for any larger
+        /// integer literal.
+        ///
+        /// *This type is available if Syn is built with the `"derive"` or
+        /// `"full"` feature.*
+        pub Int(LitInt #manual_extra_traits {
+            token: Literal,
+            // The text of `token`, kept so that methods like `suffix_str` can
+            // return a slice of it.
+            repr: String,
+            pub span: Span,
+        }),
+
+        /// A floating point literal: `1f64` or `1.0e10f64`.
+        ///
+        /// The value is decoded from the token text on demand, so it is
+        /// never NaN but is infinite for a token too large for an `f64`,
+        /// like `1e400`.
+        ///
+        /// *This type is available if Syn is built with the `"derive"` or
+        /// `"full"` feature.*
+        pub Float(LitFloat #manual_extra_traits {
+            token: Literal,
+            // The text of `token`, kept so that methods like `suffix_str` can
+            // return a slice of it.
+            repr: String,
+            pub span: Span,
+        }),
+
+        /// A boolean literal: `true` or `false`.
+        ///
+        /// *This type is available if Syn is built with the `"derive"` or
+        /// `"full"` feature.*
+        pub Bool(LitBool #manual_extra_traits {
+            pub value: bool,
+            pub span: Span,
+        }),
+
+        /// A raw token literal not interpreted by Syn, possibly because it
+        /// represents an integer larger than 64 bits.
+        ///
+        /// *This type is available if Syn is built with the `"derive"` or
+        /// `"full"` feature.*
+        pub Verbatim(LitVerbatim #manual_extra_traits {
+            pub token: Literal,
+            pub span: Span,
+        }),
+    }
+}
+
+/// Error returned when the token of a literal cannot be decoded.
+///
+/// Tokens produced by the compiler always decode successfully, but a token
+/// constructed by hand or by a buggy macro may not.
+///
+/// *This type is available if Syn is built with the `"derive"` or `"full"`
+/// feature.*
+#[derive(Debug)]
+pub struct LitError {
+    span: Span,
+    message: String,
+    raw_pounds: Option<(usize, usize)>,
+}
+
+impl LitError {
+    fn new(span: Span, message: String) -> Self {
+        LitError {
+            span: span,
+            message: message,
+            raw_pounds: None,
+        }
+    }
+
+    /// Like `new`, but records the pound mismatch if `repr` is a raw string or
+    /// raw byte string whose closing delimiter does not match its opening one.
+    fn for_token(span: Span, message: String, repr: &str) -> Self {
+        LitError {
+            span: span,
+            message: message,
+            raw_pounds: value::raw_pound_mismatch(repr),
+        }
+    }
+
+    /// The span of the literal that failed to decode.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// For a raw string whose closing delimiter is missing or does not match
+    /// the opening one, the number of `#` the closing delimiter needs and the
+    /// number it has, so that `r##"a"#` gives `Some((2, 1))`. A missing
+    /// closing quote counts as zero `#` found.
+    ///
+    /// This lets an editor offer to fix up the delimiter. It is `None` for
+    /// every other error.
+    pub fn raw_pounds(&self) -> Option<(usize, usize)> {
+        self.raw_pounds
+    }
+}
+
+impl Error for LitError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for LitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.message, f)
+    }
+}
+
+impl LitStr {
+    pub fn new(value: &str, span: Span) -> Self {
+        let token = Literal::string(value);
+        LitStr {
+            token: token,
+            span: span,
+        }
+    }
+
+    /// Like `new`, but returns an error if `value` contains a NUL character,
+    /// which would cut the string short if it were passed to C.
+    pub fn new_cstr_safe(value: &str, span: Span) -> Result<Self, LitError> {
+        match value.find('\0') {
+            Some(i) => Err(LitError::new(
+                span,
+                format!("string contains an interior nul byte at index {}", i),
+            )),
+            None => Ok(LitStr::new(value, span)),
+        }
+    }
+
+    pub fn value(&self) -> String {
+        match self.try_value() {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Like `value`, but returns an error rather than panicking if the token
+    /// is malformed, such as a hand-constructed raw string whose `#`
+    /// delimiters do not balance.
+    pub fn try_value(&self) -> Result<String, LitError> {
+        self.cooked_chars().collect()
+    }
+
+    /// Like `value`, but with every line ending in the result normalized to
+    /// `\n`.
+    ///
+    /// A line break written directly in the source as `\r\n` already decodes
+    /// to `\n` in `value`, but an escaped `\r` is kept as a carriage return
+    /// there, so `"a\r\nb"` written with escapes has the value `a`, CR, LF,
+    /// `b`. This method also turns each `\r\n` and each lone `\r` in the
+    /// decoded value into `\n`, which is what a tool comparing multi-line
+    /// strings across platforms usually wants.
+    pub fn value_normalized(&self) -> String {
+        self.value().replace("\r\n", "\n").replace('\r', "\n")
+    }
+
+    /// Checks that the token decodes successfully, returning the same error
+    /// as `try_value` if it does not, but without building the value.
+    pub fn validate(&self) -> Result<(), LitError> {
+        for ch in self.cooked_chars() {
+            ch?;
+        }
+        Ok(())
+    }
+
+    /// Like `try_value`, but fails once the decoded value grows longer than
+    /// `max` bytes rather than decoding the rest of the literal.
+    ///
+    /// This bounds the memory spent on a literal from untrusted input.
+    pub fn value_limited(&self, max: usize) -> Result<String, LitError> {
+        let mut out = String::new();
+        for ch in self.cooked_chars() {
+            let ch = ch?;
+            if out.len() + ch.len_utf8() > max {
+                return Err(LitError::new(
+                    self.span,
+                    format!("string literal is longer than the limit of {} bytes", max),
+                ));
+            }
+            out.push(ch);
+        }
+        Ok(out)
+    }
+
+    /// Like `try_value`, but fails at the first NUL character in the decoded
+    /// value, however it is written, since the value could not be passed to C
+    /// as a nul-terminated string.
+    pub fn value_cstr_safe(&self) -> Result<String, LitError> {
+        let mut out = String::new();
+        for ch in self.cooked_chars() {
+            match ch? {
+                '\0' => {
+                    return Err(LitError::new(
+                        self.span,
+                        "string literal contains a nul character".to_owned(),
+                    ))
+                }
+                ch => out.push(ch),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Whether the decoded value matches a glob `pattern`, in which `*`
+    /// matches any run of characters, including none, and `?` matches any
+    /// single character. All other characters match only themselves.
+    ///
+    /// The whole value has to match, so `"foo.rs"` matches `*.rs` and `f*`
+    /// but not `*.r`.
+    pub fn matches_glob(&self, pattern: &str) -> bool {
+        let value = self.value_chars();
+        let pattern: Vec<char> = pattern.chars().collect();
+
+        let (mut v, mut p) = (0, 0);
+        // Position of the last `*` seen and of the value when it was seen,
+        // for backtracking when the rest of the pattern fails to match.
+        let mut star = None;
+        while v < value.len() {
+            if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+                v += 1;
+                p += 1;
+            } else if p < pattern.len() && pattern[p] == '*' {
+                star = Some((p, v));
+                p += 1;
+            } else if let Some((star_p, star_v)) = star {
+                p = star_p + 1;
+                v = star_v + 1;
+                star = Some((star_p, star_v + 1));
+            } else {
+                return false;
+            }
+        }
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+
+    /// The source text of the token, quotes and escapes included, as opposed
+    /// to the decoded `value`.
+    pub fn token_str(&self) -> String {
+        self.token.to_string()
+    }
+
+    /// The text of the token between its delimiters, without decoding
+    /// escapes, so `"a\nb"` gives a backslash followed by `n` rather than a
+    /// newline.
+    ///
+    /// For a raw string this is the text between the `#"` and `"#` and is the
+    /// same as the decoded value.
+    pub fn raw_body(&self) -> String {
+        let repr = self.token.to_string();
+        if value::byte(&repr, 0) == b'r' {
+            match value::raw_body(&repr) {
+                Ok(body) => body.to_owned(),
+                Err(err) => panic!("{}", err),
+            }
+        } else {
+            repr[1..repr.len() - 1].to_owned()
+        }
+    }
+
+    /// Removes `prefix` from the start of the decoded value, or returns `None`
+    /// if the value does not start with it.
+    ///
+    /// The new literal has the same span, and a raw string stays a raw string
+    /// with the same number of `#` symbols.
+    pub fn strip_prefix(&self, prefix: &str) -> Option<LitStr> {
+        let value = self.value();
+        if !value.starts_with(prefix) {
+            return None;
+        }
+        Some(self.respelled(&value[prefix.len()..]))
+    }
+
+    /// Replaces all matches of `from` in the decoded value with `to`, with
+    /// the same semantics as `str::replace`.
+    ///
+    /// The new literal has the same span and style as this one. A raw string
+    /// gains extra `#` symbols only if the replacement would otherwise end it
+    /// early.
+    pub fn replace(&self, from: &str, to: &str) -> LitStr {
+        self.respelled(&self.value().replace(from, to))
+    }
+
+    fn respelled(&self, value: &str) -> LitStr {
+        let repr = self.token.to_string();
+        let token = if value::byte(&repr, 0) == b'r' {
+            let pounds = repr[1..].bytes().take_while(|&b| b == b'#').count();
+            let mut closing = "\"".to_owned();
+            for _ in 0..pounds {
+                closing.push('#');
+            }
+            while value.contains(&closing) {
+                closing.push('#');
+            }
+            Literal::raw_string(value, closing.len() - 1)
+        } else {
+            Literal::string(value)
+        };
+        LitStr {
+            token: token,
+            span: self.span,
+        }
+    }
+
+    /// Whether any `\x` or `\u{...}` escape in the token is written with an
+    /// uppercase hex digit, as in `"\xFF"` or `"\u{1F600}"`.
+    ///
+    /// Raw strings have no escapes and always return false.
+    pub fn has_uppercase_hex_escapes(&self) -> bool {
+        let repr = self.token.to_string();
+        value::byte(&repr, 0) != b'r' && value::has_uppercase_hex_escapes(&repr)
+    }
+
+    /// The number of escapes in the token, each one starting with a
+    /// backslash, so `"a\nb\\"` has two. A line continuation counts as one.
+    ///
+    /// Raw strings have no escapes and always return zero. A high count is a
+    /// hint that the literal would read better as a raw string.
+    pub fn escape_count(&self) -> usize {
+        let repr = self.token.to_string();
+        if value::byte(&repr, 0) == b'r' {
+            0
+        } else {
+            value::escape_count(&repr)
+        }
+    }
+
+    /// Whether the decoded value could be used as an identifier in Rust code:
+    /// it starts with an XID_Start character or an underscore, continues with
+    /// XID_Continue characters, and is neither `_` alone nor a keyword.
+    ///
+    /// The keywords are the ones the `Ident` parser rejects, reserved ones
+    /// included, so `"fn"` and `"yield"` are not valid.
+    pub fn is_valid_rust_ident(&self) -> bool {
+        let value = self.value();
+        let mut chars = value.chars();
+        let starts_ok = match chars.next() {
+            Some(first) => UnicodeXID::is_xid_start(first) || first == '_',
+            None => false,
+        };
+        starts_ok
+            && chars.all(UnicodeXID::is_xid_continue)
+            && value != "_"
+            && !ident::is_keyword(&value)
+    }
+
+    /// The decoded value as a vector of chars, for indexing by code point.
+    ///
+    /// The chars are decoded straight into the vector without building the
+    /// intermediate `String` that `value().chars().collect()` would.
+    pub fn value_chars(&self) -> Vec<char> {
+        self.cooked_chars().map(unwrap_char).collect()
+    }
+
+    /// The length in bytes of the decoded value, counted without building
+    /// the value.
+    pub fn decoded_byte_len(&self) -> usize {
+        self.decoded_lens().0
+    }
+
+    /// The number of chars in the decoded value, counted without building
+    /// the value.
+    pub fn decoded_char_len(&self) -> usize {
+        self.decoded_lens().1
+    }
+
+    fn decoded_lens(&self) -> (usize, usize) {
+        let mut bytes = 0;
+        let mut chars = 0;
+        for ch in self.cooked_chars().map(unwrap_char) {
+            bytes += ch.len_utf8();
+            chars += 1;
+        }
+        (bytes, chars)
+    }
+
+    /// Whether the decoded value starts with `prefix`, decoding only as much
+    /// of the string as it takes to find out.
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        let mut chars = self.cooked_chars().map(unwrap_char);
+        prefix.chars().all(|expected| chars.next() == Some(expected))
+    }
+
+    /// Whether the decoded value contains `needle`, decoding only up to the
+    /// end of the first match.
+    pub fn contains(&self, needle: &str) -> bool {
+        let needle: Vec<char> = needle.chars().collect();
+        if needle.is_empty() {
+            return true;
+        }
+        let mut window = Vec::with_capacity(needle.len());
+        for ch in self.cooked_chars().map(unwrap_char) {
+            if window.len() == needle.len() {
+                window.remove(0);
+            }
+            window.push(ch);
+            if window == needle {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether the decoded value is the empty string, checked without
+    /// decoding the whole string.
+    pub fn is_empty(&self) -> bool {
+        self.cooked_chars().map(unwrap_char).next().is_none()
+    }
+
+    /// Finds string literals that decode to the same value, however they are
+    /// quoted, such as `"a"` and `r"a"`.
+    ///
+    /// Each pair holds the index of the first literal with some value and the
+    /// index of a later literal with the same value, in order of the later
+    /// index. Literals that do not decode are skipped.
+    pub fn find_duplicates(lits: &[LitStr]) -> Vec<(usize, usize)> {
+        let mut first = HashMap::new();
+        let mut duplicates = Vec::new();
+        for (i, lit) in lits.iter().enumerate() {
+            if let Ok(value) = lit.try_value() {
+                match first.entry(value) {
+                    Entry::Occupied(entry) => duplicates.push((*entry.get(), i)),
+                    Entry::Vacant(entry) => {
+                        entry.insert(i);
+                    }
+                }
+            }
+        }
+        duplicates
+    }
+
+    /// Returns an iterator that decodes the chars of this string one at a
+    /// time. The methods above that look at the decoded value are built on
+    /// it, so raw and cooked strings are told apart in only one place.
+    fn cooked_chars(&self) -> LitStrChars {
+        let repr = self.token.to_string();
+        let (pos, end, raw, error) = if value::byte(&repr, 0) == b'r' {
+            let pounds = repr[1..].bytes().take_while(|&b| b == b'#').count();
+            match value::raw_body(&repr) {
+                Ok(body) => (pounds + 2, pounds + 2 + body.len(), true, None),
+                Err(message) => {
+                    let error = LitError::for_token(self.span, message, &repr);
+                    (0, 0, true, Some(error))
+                }
+            }
+        } else {
+            (1, repr.len(), false, None)
+        };
+        LitStrChars {
+            repr: repr,
+            pos: pos,
+            end: end,
+            raw: raw,
+            span: self.span,
+            error: error,
+        }
+    }
+}
+
+/// An iterator over the decoded chars of a string literal, created by
+/// `LitStr::cooked_chars`.
+///
+/// If the literal is malformed, the iterator yields the error where decoding
+/// fails and then ends.
+struct LitStrChars {
+    repr: String,
+    pos: usize,
+    end: usize,
+    raw: bool,
+    span: Span,
+    error: Option<LitError>,
+}
+
+impl Iterator for LitStrChars {
+    type Item = Result<char, LitError>;
+
+    fn next(&mut self) -> Option<Result<char, LitError>> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+        if self.pos == self.end {
+            return None;
+        }
+        if self.raw {
+            let ch = self.repr[self.pos..self.end].chars().next().unwrap();
+            self.pos += ch.len_utf8();
+            return Some(Ok(ch));
+        }
+        let mut rest = &self.repr[self.pos..self.end];
+        let next = value::next_cooked_char(&mut rest);
+        self.pos = self.end - rest.len();
+        match next {
+            Ok(Some(ch)) => Some(Ok(ch)),
+            Ok(None) => {
+                let trailing = rest != "\"";
+                self.pos = self.end;
+                if trailing {
+                    Some(Err(LitError::new(
+                        self.span,
+                        "unexpected characters after end of string literal".to_owned(),
+                    )))
+                } else {
+                    None
+                }
+            }
+            Err(message) => {
+                self.pos = self.end;
+                Some(Err(LitError::new(self.span, message)))
+            }
+        }
+    }
+}
+
+/// The char decoded by a `LitStrChars`, or a panic with its error, for the
+/// methods that panic on a malformed literal like `LitStr::value` does.
+fn unwrap_char(ch: Result<char, LitError>) -> char {
+    match ch {
+        Ok(ch) => ch,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+impl LitByteStr {
+    pub fn new(value: &[u8], span: Span) -> Self {
+        let token = Literal::byte_string(value);
+        LitByteStr {
+            token: token,
+            span: span,
+        }
+    }
+
+    pub fn value(&self) -> Vec<u8> {
+        match self.try_value() {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Like `value`, but returns an error rather than panicking if the token
+    /// is malformed.
+    pub fn try_value(&self) -> Result<Vec<u8>, LitError> {
+        let repr = self.token.to_string();
+        value::parse_lit_byte_str(&repr)
+            .map_err(|message| LitError::for_token(self.span, message, &repr))
+    }
+
+    /// Checks that the token decodes successfully, returning the same error
+    /// as `try_value` if it does not, but without building the value.
+    pub fn validate(&self) -> Result<(), LitError> {
+        let repr = self.token.to_string();
+        value::validate_lit_byte_str(&repr)
+            .map_err(|message| LitError::for_token(self.span, message, &repr))
+    }
+
+    /// The source text of the token as bytes, prefix, quotes and escapes
+    /// included, as opposed to the decoded `value`.
+    ///
+    /// This is useful for hashing a literal exactly as it was written.
+    pub fn token_bytes(&self) -> Vec<u8> {
+        self.token.to_string().into_bytes()
+    }
+
+    /// The number of escapes in the token, each one starting with a
+    /// backslash. Raw byte strings always return zero.
+    ///
+    /// See `LitStr::escape_count`.
+    pub fn escape_count(&self) -> usize {
+        let repr = self.token.to_string();
+        if value::byte(&repr, 1) == b'r' {
+            0
+        } else {
+            value::escape_count(&repr)
+        }
+    }
+
+    /// Whether the decoded value is empty, checked without decoding the whole
+    /// byte string.
+    pub fn is_empty(&self) -> bool {
+        self.bytes().next().is_none()
+    }
+
+    /// Returns an iterator that decodes the bytes of this byte string one at a
+    /// time, without building the decoded vector.
+    ///
+    /// The bytes produced are exactly those returned by `value`.
+    pub fn bytes(&self) -> LitByteStrBytes {
+        let repr = self.token.to_string();
+        assert_eq!(value::byte(&repr, 0), b'b');
+        let (pos, end, raw) = match value::byte(&repr, 1) {
+            b'"' => (2, repr.len(), false),
+            b'r' => {
+                if let Err(err) = value::raw_body(&repr[1..]) {
+                    panic!("{}", err);
+                }
+                let pounds = repr[2..].bytes().take_while(|&b| b == b'#').count();
+                (pounds + 3, repr.len() - pounds - 1, true)
+            }
+            _ => unreachable!(),
+        };
+        LitByteStrBytes {
+            repr: repr,
+            pos: pos,
+            end: end,
+            raw: raw,
+        }
+    }
+}
+
+/// An iterator over the decoded bytes of a byte string literal.
+///
+/// This struct is created by the [`bytes`] method on [`LitByteStr`].
+///
+/// [`bytes`]: struct.LitByteStr.html#method.bytes
+/// [`LitByteStr`]: struct.LitByteStr.html
+///
+/// *This type is available if Syn is built with the `"derive"` or `"full"`
+/// feature.*
+pub struct LitByteStrBytes {
+    repr: String,
+    pos: usize,
+    end: usize,
+    raw: bool,
+}
+
+impl Iterator for LitByteStrBytes {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.raw {
+            if self.pos == self.end {
+                return None;
+            }
+            self.pos += 1;
+            Some(self.repr.as_bytes()[self.pos - 1])
+        } else {
+            let mut rest = &self.repr.as_bytes()[self.pos..self.end];
+            let next = value::next_cooked_byte(&mut rest);
+            self.pos = self.end - rest.len();
+            match next {
+                Ok(next) => next,
+                Err(err) => panic!("{}", err),
+            }
+        }
+    }
+}
+
+impl LitByte {
+    pub fn new(value: u8, span: Span) -> Self {
+        let token = Literal::byte_char(value);
+        LitByte {
+            token: token,
+            span: span,
+        }
+    }
+
+    /// Builds a byte literal from the text of an escape as it would appear
+    /// between the quotes of a byte literal, such as `\x41` or `\n`, for
+    /// example when the escape comes from a configuration file. A single
+    /// ASCII character other than a backslash or a quote stands for itself;
+    /// the quote has to be written `\'`, as in a byte literal.
+    ///
+    /// The escape is decoded the same way as in `b'...'`, with the same
+    /// errors for an invalid one.
+    pub fn from_escape_str(s: &str, span: Span) -> Result<LitByte, LitError> {
+        if s.is_empty() {
+            return Err(LitError::new(span, "empty byte escape".to_owned()));
+        }
+        if s == "'" {
+            return Err(LitError::new(span, "unescaped ' in byte escape; use \\'".to_owned()));
+        }
+        match value::parse_lit_byte(&format!("b'{}'", s)) {
+            Ok(value) => Ok(LitByte::new(value, span)),
+            Err(message) => Err(LitError::new(span, message)),
+        }
+    }
+
+    pub fn value(&self) -> u8 {
+        match self.try_value() {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Like `value`, but returns an error rather than panicking if the token
+    /// is malformed.
+    pub fn try_value(&self) -> Result<u8, LitError> {
+        let repr = self.token.to_string();
+        value::parse_lit_byte(&repr).map_err(|message| LitError::new(self.span, message))
+    }
+
+    /// Checks that the token decodes successfully, returning the same error
+    /// as `try_value` if it does not.
+    pub fn validate(&self) -> Result<(), LitError> {
+        self.try_value().map(|_| ())
+    }
+
+    /// The equivalent char literal with the same span, or `None` if the byte
+    /// is not ASCII.
+    pub fn to_lit_char(&self) -> Option<LitChar> {
+        let value = self.value();
+        if value < 0x80 {
+            Some(LitChar::new(char::from(value), self.span))
+        } else {
+            None
+        }
+    }
+}
+
+impl LitChar {
+    pub fn new(value: char, span: Span) -> Self {
+        let token = Literal::character(value);
+        LitChar {
+            token: token,
+            span: span,
+        }
+    }
+
+    /// Like `new`, but spelled for a human reader: the character is written
+    /// as is when it is visible, and otherwise with the shortest escape that
+    /// names it, like `'\n'` or `'\u{7f}'`. `new` escapes every character
+    /// outside printable ASCII, so `'é'` comes out as `'\u{e9}'` there.
+    ///
+    /// Control characters, whitespace other than a space, and invisible
+    /// formatting characters like the zero-width and text direction ones are
+    /// escaped, as are the quote and the backslash. So are combining marks,
+    /// which would be drawn on top of the opening quote, private-use
+    /// characters, which have no agreed glyph, and noncharacters.
+    pub fn best(value: char, span: Span) -> Self {
+        let repr = match value {
+            '\n' => "'\\n'".to_owned(),
+            '\r' => "'\\r'".to_owned(),
+            '\t' => "'\\t'".to_owned(),
+            '\0' => "'\\0'".to_owned(),
+            '\'' => "'\\''".to_owned(),
+            '\\' => "'\\\\'".to_owned(),
+            ' ' => "' '".to_owned(),
+            _ if value::is_visible(value) => format!("'{}'", value),
+            _ => format!("'\\u{{{:x}}}'", value as u32),
+        };
+        LitChar {
+            token: value::to_literal(&repr),
+            span: span,
+        }
+    }
+
+    pub fn value(&self) -> char {
+        match self.try_value() {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Like `value`, but returns an error rather than panicking if the token
+    /// is malformed.
+    pub fn try_value(&self) -> Result<char, LitError> {
+        let repr = self.token.to_string();
+        value::parse_lit_char(&repr).map_err(|message| LitError::new(self.span, message))
+    }
+
+    /// Checks that the token decodes successfully, returning the same error
+    /// as `try_value` if it does not.
+    pub fn validate(&self) -> Result<(), LitError> {
+        self.try_value().map(|_| ())
+    }
+
+    /// The Unicode scalar value of the character, like `0x1F600` for `'😀'`.
+    pub fn codepoint(&self) -> u32 {
+        self.value() as u32
+    }
+
+    /// Whether the character is in the ASCII range.
+    pub fn is_ascii(&self) -> bool {
+        self.codepoint() < 0x80
+    }
+
+    /// The casing of the hex digits if the character is written as a
+    /// `\u{...}` escape, or `None` if it is written some other way.
+    ///
+    /// This is `Some(false)` if any hex digit is a lowercase letter and
+    /// `Some(true)` otherwise, so an escape like `\u{41}` with no letters
+    /// counts as uppercase.
+    pub fn unicode_escape_uppercase(&self) -> Option<bool> {
+        let repr = self.token.to_string();
+        if !repr.starts_with("'\\u{") {
+            return None;
+        }
+        Some(!repr[4..].bytes().any(|b| b'a' <= b && b <= b'f'))
+    }
+
+    /// Returns a copy of this literal written as a `\u{...}` escape with the
+    /// shortest hex digits in the given case, like `'\u{1F600}'`.
+    pub fn with_unicode_escape(&self, upper: bool) -> LitChar {
+        let repr = if upper {
+            format!("'\\u{{{:X}}}'", self.codepoint())
+        } else {
+            format!("'\\u{{{:x}}}'", self.codepoint())
+        };
+        let token = value::to_literal(&repr);
+        LitChar {
+            token: token,
+            span: self.span,
+        }
+    }
+
+    /// The equivalent byte literal with the same span, or `None` if the char
+    /// is not ASCII.
+    pub fn to_lit_byte(&self) -> Option<LitByte> {
+        if self.is_ascii() {
+            Some(LitByte::new(self.value() as u8, self.span))
+        } else {
+            None
+        }
+    }
+
+    /// The character escaped the way `char::escape_default` escapes it,
+    /// without surrounding quotes, for splicing into a larger generated
+    /// string literal.
+    ///
+    /// A newline becomes `\n` and a printable ASCII character like `a` stays
+    /// as it is.
+    pub fn to_escaped_string(&self) -> String {
+        self.value().escape_default().collect()
+    }
+}
+
+impl LitInt {
+    pub fn new(value: u64, suffix: IntSuffix, span: Span) -> Self {
+        let token = match suffix {
+            IntSuffix::Isize => Literal::isize(value as isize),
+            IntSuffix::I8 => Literal::i8(value as i8),
+            IntSuffix::I16 => Literal::i16(value as i16),
+            IntSuffix::I32 => Literal::i32(value as i32),
+            IntSuffix::I64 => Literal::i64(value as i64),
+            IntSuffix::I128 => value::to_literal(&format!("{}i128", value)),
+            IntSuffix::Usize => Literal::usize(value as usize),
+            IntSuffix::U8 => Literal::u8(value as u8),
+            IntSuffix::U16 => Literal::u16(value as u16),
+            IntSuffix::U32 => Literal::u32(value as u32),
+            IntSuffix::U64 => Literal::u64(value),
+            IntSuffix::U128 => value::to_literal(&format!("{}u128", value)),
+            IntSuffix::None => if value <= i64::max_value() as u64 {
+                Literal::integer(value as i64)
+            } else {
+                value::to_literal(&value.to_string())
+            },
+        };
+        LitInt::from_token(token, span)
+    }
+
+    fn from_token(token: Literal, span: Span) -> Self {
+        LitInt {
+            repr: token.to_string(),
+            token: token,
+            span: span,
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        value::parse_lit_int(&self.repr).unwrap()
+    }
+
+    /// The value of the literal widened to `u128`.
+    ///
+    /// *This method is available if Syn is built with the `"i128"` feature.*
+    #[cfg(feature = "i128")]
+    pub fn value_u128(&self) -> u128 {
+        value::parse_int_u128(&self.repr).unwrap()
+    }
+
+    /// Parses an integer written in any radix from 2 to 36, such as a base-36
+    /// identifier carried inside a string literal.
+    ///
+    /// Digits past 9 are the letters `a` to `z` in either case, and
+    /// underscores are skipped as they are in Rust integer literals. Returns
+    /// `None` if the string has no digits, contains a character that is not a
+    /// digit in this radix, or does not fit in a `u128`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "i128")]
+    /// # fn main() {
+    /// use syn::LitInt;
+    ///
+    /// assert_eq!(LitInt::parse_radix("zz", 36), Some(1295));
+    /// assert_eq!(LitInt::parse_radix("1010_1010", 2), Some(0xaa));
+    /// assert_eq!(LitInt::parse_radix("12", 2), None);
+    /// # }
+    /// # #[cfg(not(feature = "i128"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range 2 to 36.
+    ///
+    /// *This method is available if Syn is built with the `"i128"` feature.*
+    #[cfg(feature = "i128")]
+    pub fn parse_radix(s: &str, radix: u32) -> Option<u128> {
+        value::parse_int_radix(s, radix)
+    }
+
+    /// Formats the value in the given radix, with a `0b`, `0o` or `0x` prefix
+    /// for radix 2, 8 or 16 and no prefix otherwise, regardless of how the
+    /// literal was written. Digits above 9 are lowercase and there is no
+    /// suffix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range 2 to 36.
+    pub fn to_radix_string(&self, radix: u32) -> String {
+        let prefix = match radix {
+            2 => "0b",
+            8 => "0o",
+            16 => "0x",
+            _ => "",
+        };
+        format!("{}{}", prefix, value::format_int_radix(self.value(), radix))
+    }
+
+    /// The type suffix of this literal.
+    ///
+    /// A suffix that does not name a known integer type, like the `u256` in
+    /// `1u256`, is reported as `IntSuffix::None`; use `suffix_str` to get its
+    /// text.
+    pub fn suffix(&self) -> IntSuffix {
+        value::int_suffix_from_str(self.suffix_str())
+    }
+
+    /// The suffix exactly as written in the token, like `"u8"` for `1_u8`, or
+    /// an empty string if there is none.
+    ///
+    /// This is everything after the digits, so it is also available for
+    /// suffixes that `IntSuffix` has no variant for.
+    pub fn suffix_str(&self) -> &str {
+        value::split_suffix(&self.repr).1
+    }
+
+    /// The value of this literal along with its suffix as written, treated as
+    /// a unit of measure, so `1024MiB` gives `(1024, "MiB")` and `10` gives
+    /// `(10, "")`.
+    ///
+    /// Unlike `suffix`, the unit does not have to name an integer type.
+    pub fn split_unit(&self) -> (u64, &str) {
+        (self.value(), self.suffix_str())
+    }
+
+    /// The digits exactly as written in the token, without the radix prefix
+    /// or suffix but with any underscores, so `0xFF_00u16` gives `"FF_00"`.
+    pub fn digits(&self) -> &str {
+        let digits = value::split_suffix(&self.repr).0;
+        if self.radix() == 10 {
+            digits
+        } else {
+            &digits[2..]
+        }
+    }
+
+    /// The value this literal would have once stored in the type named by
+    /// `suffix`, wrapping around if it does not fit, so `200` gives 200 as a
+    /// `u8` but -56 as an `i8`, and `255` gives -1 as an `i8`.
+    ///
+    /// The suffix of the literal itself is ignored, so an unsuffixed literal
+    /// can be wrapped to any width; pass `self.suffix()` to use the type the
+    /// literal declares. `isize` and `usize` are taken to be 64 bits.
+    /// `IntSuffix::None` gives the value as is.
+    ///
+    /// *This method is available if Syn is built with the `"i128"` feature.*
+    #[cfg(feature = "i128")]
+    pub fn wrapping_value_i128(&self, suffix: IntSuffix) -> i128 {
+        let value = self.value();
+        match suffix {
+            IntSuffix::I8 => i128::from(value as i8),
+            IntSuffix::I16 => i128::from(value as i16),
+            IntSuffix::I32 => i128::from(value as i32),
+            IntSuffix::I64 | IntSuffix::Isize => i128::from(value as i64),
+            IntSuffix::U8 => i128::from(value as u8),
+            IntSuffix::U16 => i128::from(value as u16),
+            IntSuffix::U32 => i128::from(value as u32),
+            IntSuffix::U64
+            | IntSuffix::Usize
+            | IntSuffix::I128
+            | IntSuffix::U128
+            | IntSuffix::None => i128::from(value),
+        }
+    }
+
+    /// Whether the value of this literal is in range for the type named by
+    /// `suffix`, whatever suffix the literal itself has. `IntSuffix::None`
+    /// accepts any value.
+    ///
+    /// `isize` and `usize` are taken to be 64 bits wide, the same assumption
+    /// `checked_add` makes, so a value that fits `i64` fits `isize` even
+    /// though it might not on a 32-bit target.
+    ///
+    /// Like `minimal_suffix`, this goes by the magnitude alone since a
+    /// `LitInt` is never negative.
+    pub fn fits_in(&self, suffix: IntSuffix) -> bool {
+        self.value() <= value::int_suffix_max(&suffix)
+    }
+
+    /// The narrowest integer suffix of the requested signedness whose type can
+    /// hold the value of this literal.
+    ///
+    /// Unsigned suffixes go from `u8` up to `u64`, which holds every value a
+    /// `LitInt` can. Signed suffixes go from `i8` up to `i128`, which is needed
+    /// for values above `i64::MAX`. `isize` and `usize` are never chosen since
+    /// their width depends on the target.
+    ///
+    /// A `LitInt` is never negative; a literal like `-1` is a `-` token
+    /// followed by `1`, and `1` needs only `i8` when `signed` is true. Going by
+    /// the magnitude alone means the most negative value of a type, like the
+    /// `128` in `-128`, is given the next wider suffix.
+    pub fn minimal_suffix(&self, signed: bool) -> IntSuffix {
+        let value = self.value();
+        if signed {
+            if value <= i8::max_value() as u64 {
+                IntSuffix::I8
+            } else if value <= i16::max_value() as u64 {
+                IntSuffix::I16
+            } else if value <= i32::max_value() as u64 {
+                IntSuffix::I32
+            } else if value <= i64::max_value() as u64 {
+                IntSuffix::I64
+            } else {
+                IntSuffix::I128
+            }
+        } else if value <= u64::from(u8::max_value()) {
+            IntSuffix::U8
+        } else if value <= u64::from(u16::max_value()) {
+            IntSuffix::U16
+        } else if value <= u64::from(u32::max_value()) {
+            IntSuffix::U32
+        } else {
+            IntSuffix::U64
+        }
+    }
+
+    /// Replaces the suffix of this literal, keeping the digits exactly as
+    /// written, including radix prefix and underscores.
+    ///
+    /// `0xFF_u8.with_suffix(IntSuffix::U16)` gives `0xFF_u16`. The new literal
+    /// has the same span.
+    pub fn with_suffix(&self, suffix: IntSuffix) -> LitInt {
+        let digits = value::split_suffix(&self.repr).0;
+        self.respelled(digits, value::int_suffix_str(&suffix))
+    }
+
+    /// Removes the suffix of this literal, keeping the digits exactly as
+    /// written apart from any `_` that separated them from the suffix.
+    pub fn unsuffixed(&self) -> LitInt {
+        let digits = value::split_suffix(&self.repr).0;
+        self.respelled(digits.trim_right_matches('_'), "")
+    }
+
+    fn respelled(&self, digits: &str, suffix: &str) -> LitInt {
+        let token = value::to_literal(&format!("{}{}", digits, suffix));
+        LitInt::from_token(token, self.span)
+    }
+
+    /// Whether this is a decimal literal with a redundant leading `0`, as in
+    /// `0755`.
+    ///
+    /// Such literals are decimal in Rust but are frequently meant as octal by
+    /// someone used to C. Returns false for `0` itself and for literals with a
+    /// `0x`, `0o` or `0b` radix prefix.
+    pub fn has_leading_zero(&self) -> bool {
+        let value = &self.repr;
+        match (value::byte(value, 0), value::byte(value, 1)) {
+            (b'0', b'x') | (b'0', b'o') | (b'0', b'b') => false,
+            (b'0', _) => value[1..]
+                .bytes()
+                .find(|&b| b != b'_')
+                .map_or(false, |b| b'0' <= b && b <= b'9'),
+            _ => false,
+        }
+    }
+
+    /// The radix the literal is written in: 16, 8 or 2 for a literal with a
+    /// `0x`, `0o` or `0b` prefix, and 10 otherwise.
+    pub fn radix(&self) -> u32 {
+        let repr = &self.repr;
+        match (value::byte(repr, 0), value::byte(repr, 1)) {
+            (b'0', b'x') => 16,
+            (b'0', b'o') => 8,
+            (b'0', b'b') => 2,
+            _ => 10,
+        }
+    }
+
+    /// Whether this is a hexadecimal literal whose letter digits are written
+    /// in uppercase, as in `0xFF`.
+    ///
+    /// Returns false for literals in other radixes and for hexadecimal ones
+    /// with no letter digits.
+    pub fn hex_is_uppercase(&self) -> bool {
+        if self.radix() != 16 {
+            return false;
+        }
+        let digits = value::split_suffix(&self.repr).0;
+        for b in digits[2..].bytes() {
+            match b {
+                b'A'...b'F' => return true,
+                b'a'...b'f' => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Applies `f` to the value of this literal, keeping its suffix and span.
+    ///
+    /// If `preserve_radix` is true the result is written in the same radix as
+    /// this literal, and a hexadecimal one keeps the case of its digits, so
+    /// that `0xFF` stays hexadecimal rather than turning into `255`. Otherwise
+    /// the result is written in decimal. Digit separators are not preserved.
+    ///
+    /// Returns `None` if the new value is out of range for the suffix, as
+    /// `0xFFu8` plus one would be, or if the suffix is one the lexer cannot
+    /// build a token with, like the `ms` in `1ms`.
+    pub fn map_value<F>(&self, preserve_radix: bool, f: F) -> Option<LitInt>
+    where
+        F: FnOnce(u64) -> u64,
+    {
+        let radix = if preserve_radix { self.radix() } else { 10 };
+        let value = f(self.value());
+        if value > value::int_suffix_max(&self.suffix()) {
+            return None;
+        }
+        self.rebuild(value, radix, self.suffix_str())
+    }
+
+    /// A literal with the given value, span of `self`, and suffix, written in
+    /// the given radix using the hex digit case of `self`, or `None` if the
+    /// suffix does not lex.
+    fn rebuild(&self, value: u64, radix: u32, suffix: &str) -> Option<LitInt> {
+        let digits = match radix {
+            16 if self.hex_is_uppercase() => format!("0x{:X}", value),
+            16 => format!("0x{:x}", value),
+            8 => format!("0o{:o}", value),
+            2 => format!("0b{:b}", value),
+            _ => value.to_string(),
+        };
+        value::try_to_literal(&format!("{}{}", digits, suffix))
+            .map(|token| LitInt::from_token(token, self.span))
+    }
+
+    /// Adds two integer literals, returning `None` on overflow, if the
+    /// literals have different suffixes, or if either suffix does not name an
+    /// integer type, like the `ms` in `1ms`.
+    ///
+    /// The result keeps the suffix of `self`, or takes that of `rhs` if `self`
+    /// is unsuffixed, and has the span of `self`. It is written in the same
+    /// radix as `self`; use `map_value` on the result to switch to decimal.
+    /// Overflow is checked against the range of the resulting suffix, taking
+    /// `isize` and `usize` to be 64 bits, as well as against the 64 bits that
+    /// a `LitInt` can hold.
+    pub fn checked_add(&self, rhs: &LitInt) -> Option<LitInt> {
+        self.checked_op(rhs, u64::checked_add)
+    }
+
+    /// Subtracts `rhs` from this integer literal, returning `None` on overflow
+    /// or if the literals have different suffixes.
+    ///
+    /// Suffix and overflow behavior are the same as for `checked_add`.
+    pub fn checked_sub(&self, rhs: &LitInt) -> Option<LitInt> {
+        self.checked_op(rhs, u64::checked_sub)
+    }
+
+    /// Multiplies two integer literals, returning `None` on overflow or if the
+    /// literals have different suffixes.
+    ///
+    /// Suffix and overflow behavior are the same as for `checked_add`.
+    pub fn checked_mul(&self, rhs: &LitInt) -> Option<LitInt> {
+        self.checked_op(rhs, u64::checked_mul)
+    }
+
+    fn checked_op<F>(&self, rhs: &LitInt, op: F) -> Option<LitInt>
+    where
+        F: FnOnce(u64, u64) -> Option<u64>,
+    {
+        let suffix_str = match (self.suffix_str(), rhs.suffix_str()) {
+            ("", suffix) | (suffix, "") => suffix,
+            (lhs, rhs) => if lhs == rhs {
+                lhs
+            } else {
+                return None;
+            },
+        };
+        let suffix = value::int_suffix_from_str(suffix_str);
+        if let IntSuffix::None = suffix {
+            if !suffix_str.is_empty() {
+                return None;
+            }
+        }
+        match op(self.value(), rhs.value()) {
+            Some(value) if value <= value::int_suffix_max(&suffix) => {
+                self.rebuild(value, self.radix(), suffix_str)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl LitFloat {
+    pub fn new(value: f64, suffix: FloatSuffix, span: Span) -> Self {
+        let token = match suffix {
+            FloatSuffix::F32 => Literal::f32(value as f32),
+            FloatSuffix::F64 => Literal::f64(value),
+            FloatSuffix::None => Literal::float(value),
+        };
+        LitFloat::from_token(token, span)
+    }
+
+    fn from_token(token: Literal, span: Span) -> Self {
+        LitFloat {
+            repr: token.to_string(),
+            token: token,
+            span: span,
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        match self.try_value() {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Like `value`, but returns an error rather than panicking if the token
+    /// is malformed, for example `2.0ef64` with no digits in the exponent.
+    pub fn try_value(&self) -> Result<f64, LitError> {
+        value::parse_lit_float(&self.repr).map_err(|message| LitError::new(self.span, message))
+    }
+
+    /// The category of the value of the literal as an `f64`.
+    ///
+    /// A literal is never NaN. It is normally `Normal` or `Zero`, but a tiny
+    /// literal like `1e-310` is `Subnormal`, which is often a sign of a
+    /// mistake. `Infinite` is only possible for a token too large for an
+    /// `f64`, like `1e400`. `LitFloat::new` never builds one, but the parser
+    /// accepts such a token because it is lexically valid.
+    pub fn classify(&self) -> FpCategory {
+        self.value().classify()
+    }
+
+    /// The value of the literal rounded to `f32`, or `None` if it is too large
+    /// for an `f32` and would round to infinity, as `1e40` does.
+    pub fn to_f32_checked(&self) -> Option<f32> {
+        let value = self.value() as f32;
+        if value.is_infinite() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Returns the number as written in the source, with underscores and any
+    /// suffix removed.
+    ///
+    /// Unlike `value`, this does not round-trip through `f64` so no precision
+    /// is lost for literals with more significant digits than an `f64` holds.
+    pub fn value_str(&self) -> String {
+        value::float_digits(&self.repr)
+    }
+
+    pub fn suffix(&self) -> FloatSuffix {
+        value::float_suffix_from_str(self.suffix_str())
+    }
+
+    /// The suffix exactly as written in the token, like `"f32"` for
+    /// `1.0_f32`, or an empty string if there is none.
+    ///
+    /// This is everything after the number, so it is also available for
+    /// suffixes that `FloatSuffix` has no variant for, like `f16`.
+    pub fn suffix_str(&self) -> &str {
+        value::split_suffix(&self.repr).1
+    }
+
+    /// The value of this literal along with whatever follows the number,
+    /// treated as a unit of measure, so `5.0ms` gives `(5.0, "ms")`.
+    ///
+    /// Unlike `suffix`, the unit does not have to name a float type, and
+    /// unlike `value` this works for units that do not begin with `f`. An `e`
+    /// begins the exponent only if it is followed by a digit, optionally after
+    /// a sign, so `1.5em` gives `(1.5, "em")`.
+    pub fn split_unit(&self) -> (f64, &str) {
+        let (number, unit) = value::split_suffix(&self.repr);
+        let value = match value::parse_lit_float(number) {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        };
+        (value, unit)
+    }
+
+    /// The number exactly as written in the token, without the suffix but
+    /// with any underscores, so `1_000.5e3_f32` gives `"1_000.5e3_"`.
+    ///
+    /// Unlike `value_str`, this keeps the underscores.
+    pub fn digits(&self) -> &str {
+        value::split_suffix(&self.repr).0
+    }
+
+    /// Replaces the suffix of this literal, keeping the mantissa and exponent
+    /// exactly as written rather than reformatting the value.
+    ///
+    /// `1E10f64.with_suffix(FloatSuffix::F32)` gives `1E10f32`. A mantissa
+    /// ending in `.`, like `1.`, gains a `0` when a suffix is added since
+    /// `1.f32` would not be a float literal. The new literal has the same
+    /// span.
+    pub fn with_suffix(&self, suffix: FloatSuffix) -> LitFloat {
+        let suffix = value::float_suffix_str(&suffix);
+        if suffix.is_empty() {
+            return self.unsuffixed();
+        }
+        let mut digits = self.digits().to_owned();
+        if digits.ends_with('.') {
+            digits.push('0');
+        }
+        self.respelled(&digits, suffix)
+    }
+
+    /// Removes the suffix of this literal, keeping the mantissa and exponent
+    /// exactly as written, so `1E10f64` becomes `1E10`.
+    ///
+    /// A `_` that separated the suffix is dropped, and a literal like `1f32`
+    /// that has neither a `.` nor an exponent gains a `.0` so that it remains
+    /// a float.
+    pub fn unsuffixed(&self) -> LitFloat {
+        let digits = self.digits().trim_right_matches('_');
+        if digits.contains(|c| c == '.' || c == 'e' || c == 'E') {
+            self.respelled(digits, "")
+        } else {
+            self.respelled(digits, ".0")
+        }
+    }
+
+    /// Builds a literal for `value` written in the same style as this one, or
+    /// returns `None` if `value` is negative, infinite or NaN and so cannot be
+    /// written as a float literal. With an `f32` suffix, `value` must also be
+    /// finite once converted to `f32`.
+    ///
+    /// If `value` is equal to the value of this literal, the result has a
+    /// byte-identical token. Otherwise the shortest representation of `value`
+    /// is laid out following these rules:
+    ///
+    /// - A literal written with an exponent, like `1.0e6`, keeps using one,
+    ///   with the same `e` or `E` and the same explicit `+` sign if any.
+    /// - A literal written without an exponent switches to one for a value
+    ///   below `1e-4` or at least `1e16`, so `1.5` with a value of `1e300`
+    ///   becomes `1.0e300` rather than a 301 digit number.
+    /// - A literal written with a `.` gets a `.` in its mantissa, so `1.0e6`
+    ///   becomes `2.0e6` rather than `2e6` and `1.5` becomes `2.0`.
+    /// - The suffix, and a `_` in front of it, are kept as written.
+    ///
+    /// Underscores within the digits are not carried over.
+    pub fn with_value_preserving_format(&self, value: f64) -> Option<LitFloat> {
+        if value == self.value() && value.is_sign_positive() {
+            return Some(LitFloat {
+                token: self.token.clone(),
+                repr: self.repr.clone(),
+                span: self.span,
+            });
+        }
+        if !value.is_finite() || value.is_sign_negative() {
+            return None;
+        }
+        let is_f32 = self.suffix_str() == "f32";
+        if is_f32 && !(value as f32).is_finite() {
+            return None;
+        }
+
+        let (written, suffix) = value::split_suffix(&self.repr);
+        let separator = if written.ends_with('_') { "_" } else { "" };
+        let exponent = written.find(|c| c == 'e' || c == 'E');
+        let has_dot = written[..exponent.unwrap_or_else(|| written.len())].contains('.');
+        let is_extreme = value != 0.0 && (value < 1e-4 || value >= 1e16);
+
+        let mut digits = if exponent.is_some() || is_extreme {
+            if is_f32 {
+                format!("{:e}", value as f32)
+            } else {
+                format!("{:e}", value)
+            }
+        } else if is_f32 {
+            format!("{}", value as f32)
+        } else {
+            format!("{}", value)
+        };
+        if let Some(e) = digits.find('e') {
+            if has_dot && !digits[..e].contains('.') {
+                digits = format!("{}.0{}", &digits[..e], &digits[e..]);
+            }
+            if let Some(i) = exponent {
+                let e = digits.find('e').unwrap();
+                if written[i + 1..].starts_with('+') && !digits[e + 1..].starts_with('-') {
+                    digits.insert(e + 1, '+');
+                }
+                if written.as_bytes()[i] == b'E' {
+                    digits = digits.replace('e', "E");
+                }
+            }
+        } else if has_dot && !digits.contains('.') {
+            digits.push_str(".0");
+        }
+
+        Some(self.respelled(&format!("{}{}", digits, separator), suffix))
+    }
+
+    fn respelled(&self, digits: &str, suffix: &str) -> LitFloat {
+        let token = value::to_literal(&format!("{}{}", digits, suffix));
+        LitFloat::from_token(token, self.span)
+    }
+
+    /// Whether this literal ends in a `.` with no fractional digits, as in
+    /// `1.`, which a formatter may want to normalize to `1.0`.
+    pub fn has_trailing_dot(&self) -> bool {
+        self.digits().ends_with('.')
+    }
+
+    /// Whether the exponent of this literal is written with an explicit plus
+    /// sign, as in `1e+10`.
+    pub fn exponent_has_plus(&self) -> bool {
+        let repr = &self.repr;
+        match repr.find(|c| c == 'e' || c == 'E') {
+            Some(i) => repr[i + 1..].starts_with('+'),
+            None => false,
+        }
+    }
+
+    /// Rewrites the exponent of this literal to either always or never carry
+    /// an explicit plus sign, leaving the mantissa, a minus sign on the
+    /// exponent, and the suffix exactly as written.
+    ///
+    /// `1e+10` and `1e10` convert into one another; `1e-10` and literals
+    /// without an exponent are returned unchanged.
+    pub fn normalize_exponent(&self, keep_plus: bool) -> LitFloat {
+        let mut repr = self.repr.clone();
+        if let Some(i) = repr.find(|c| c == 'e' || c == 'E') {
+            let has_plus = repr[i + 1..].starts_with('+');
+            let has_minus = repr[i + 1..].starts_with('-');
+            if has_plus && !keep_plus {
+                repr.remove(i + 1);
+            } else if !has_plus && !has_minus && keep_plus {
+                repr.insert(i + 1, '+');
+            }
+        }
+        let token = if repr == self.repr {
+            self.token.clone()
+        } else {
+            value::to_literal(&repr)
+        };
+        LitFloat::from_token(token, self.span)
+    }
+}
+
+impl LitVerbatim {
+    /// The value of an integer token too large for a `LitInt` written out in
+    /// decimal, without underscores or suffix, or `None` if the token is not
+    /// an integer.
+    ///
+    /// `0x1_0000_0000_0000_0000u128` gives `"18446744073709551616"`. The
+    /// conversion works on the digits directly, so there is no limit on the
+    /// size of the value.
+    pub fn int_decimal(&self) -> Option<String> {
+        value::int_decimal(&self.token.to_string())
+    }
+}
+
+impl LitBool {
+    /// The span of the `true` or `false` keyword.
+    ///
+    /// This is the same as `span`, since the keyword is the whole literal,
+    /// and is provided so that diagnostics can highlight the keyword the same
+    /// way for every kind of literal.
+    pub fn keyword_span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Lit {
+    /// Creates a `Lit::Bool`, for turning a dynamically typed config value
+    /// into a literal.
+    pub fn from_bool(value: bool, span: Span) -> Self {
+        Lit::Bool(LitBool {
+            value: value,
+            span: span,
+        })
+    }
+
+    /// Creates an unsuffixed `Lit::Int`, for turning a dynamically typed
+    /// config value into a literal.
+    ///
+    /// Rust has no negative literals, so the literal holds the magnitude of
+    /// `value` and a negative value sets `negative` on the result, which is
+    /// printed as a `-` followed by the literal.
+    pub fn from_i64(value: i64, span: Span) -> SignedLit {
+        SignedLit {
+            negative: value < 0,
+            // The magnitude of `i64::MIN` only fits once widened to `u64`.
+            lit: Lit::Int(LitInt::new(value.wrapping_abs() as u64, IntSuffix::None, span)),
+        }
+    }
+
+    /// Creates an unsuffixed `Lit::Float`, for turning a dynamically typed
+    /// config value into a literal.
+    ///
+    /// As with `from_i64`, a negative value sets `negative` on the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is infinite or NaN.
+    pub fn from_f64(value: f64, span: Span) -> SignedLit {
+        SignedLit {
+            // Negative zero is not negative, and is printed as `0.0`.
+            negative: value < 0.0,
+            lit: Lit::Float(LitFloat::new(value.abs(), FloatSuffix::None, span)),
+        }
+    }
+
+    /// Creates a `Lit::Str`, for turning a dynamically typed config value into
+    /// a literal.
+    pub fn from_string(value: &str, span: Span) -> Self {
+        Lit::Str(LitStr::new(value, span))
+    }
+
+    /// Creates a `Lit::Char`.
+    pub fn from_char(value: char, span: Span) -> Self {
+        Lit::Char(LitChar::new(value, span))
+    }
+
+    /// Creates a `Lit::Byte`.
+    pub fn from_byte(value: u8, span: Span) -> Self {
+        Lit::Byte(LitByte::new(value, span))
+    }
+
+    /// Creates a `Lit::ByteStr`.
+    pub fn from_bytes(value: &[u8], span: Span) -> Self {
+        Lit::ByteStr(LitByteStr::new(value, span))
+    }
+
+    /// Creates the literal closest to a scalar JSON value: a string, an
+    /// unsuffixed integer or float, or a boolean.
+    ///
+    /// Numbers follow `from_i64` and `from_f64`, so negative values come back
+    /// with `negative` set. Returns an error for `Scalar::Null`, which has no
+    /// literal equivalent.
+    pub fn from_json_scalar(value: &Scalar, span: Span) -> Result<SignedLit, LitError> {
+        let lit = match *value {
+            Scalar::Null => {
+                return Err(LitError::new(
+                    span,
+                    "JSON null cannot be represented as a literal".to_owned(),
+                ))
+            }
+            Scalar::Bool(value) => Lit::from_bool(value, span),
+            Scalar::Int(value) => return Ok(Lit::from_i64(value, span)),
+            Scalar::Float(value) => return Ok(Lit::from_f64(value, span)),
+            Scalar::String(ref value) => Lit::from_string(value, span),
+        };
+        Ok(SignedLit {
+            negative: false,
+            lit: lit,
+        })
+    }
+
+    /// Creates an integer literal from a value of any size.
+    ///
+    /// A value that fits in a `u64`, with a suffix of 64 bits or less or no
+    /// suffix, produces a `Lit::Int` built by `LitInt::new`. Anything else
+    /// produces a `Lit::Verbatim` with the same token text, like
+    /// `18446744073709551616u128`, so a `u128` or `i128` literal is always
+    /// verbatim even when its value is small. This differs from `Lit::new`,
+    /// which gives a `Lit::Int` for a token like `5u128`.
+    ///
+    /// As with `LitInt::new`, the value is not checked against the range of
+    /// the suffix.
+    ///
+    /// *This method is available if Syn is built with the `"i128"` feature.*
+    #[cfg(feature = "i128")]
+    pub fn int_u128(value: u128, suffix: IntSuffix, span: Span) -> Self {
+        let wide_suffix = match suffix {
+            IntSuffix::I128 | IntSuffix::U128 => true,
+            _ => false,
+        };
+        if !wide_suffix && value <= u128::from(u64::max_value()) {
+            return Lit::Int(LitInt::new(value as u64, suffix, span));
+        }
+        let token = value::to_literal(&format!("{}{}", value, value::int_suffix_str(&suffix)));
+        Lit::Verbatim(LitVerbatim {
+            token: token,
+            span: span,
+        })
+    }
+
+    /// Converts a single token tree into a literal, or returns `None` if it
+    /// is neither a literal token nor the keyword `true` or `false`.
+    ///
+    /// The literal takes the span of the token tree.
+    pub fn from_token_tree(tt: &TokenTree) -> Option<Self> {
+        match tt.kind {
+            TokenNode::Literal(ref lit) => Some(Lit::new(lit.clone(), tt.span)),
+            TokenNode::Term(term) => match term.as_str() {
+                "true" => Some(Lit::from_bool(true, tt.span)),
+                "false" => Some(Lit::from_bool(false, tt.span)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The span of this literal, regardless of which kind of literal it is.
+    pub fn span(&self) -> Span {
+        match *self {
+            Lit::Str(ref lit) => lit.span,
+            Lit::ByteStr(ref lit) => lit.span,
+            Lit::Byte(ref lit) => lit.span,
+            Lit::Char(ref lit) => lit.span,
+            Lit::Int(ref lit) => lit.span,
+            Lit::Float(ref lit) => lit.span,
+            Lit::Bool(ref lit) => lit.span,
+            Lit::Verbatim(ref lit) => lit.span,
+        }
+    }
+
+    /// Replaces the span of this literal, regardless of which kind of literal
+    /// it is.
+    pub fn set_span(&mut self, span: Span) {
+        match *self {
+            Lit::Str(ref mut lit) => lit.span = span,
+            Lit::ByteStr(ref mut lit) => lit.span = span,
+            Lit::Byte(ref mut lit) => lit.span = span,
+            Lit::Char(ref mut lit) => lit.span = span,
+            Lit::Int(ref mut lit) => lit.span = span,
+            Lit::Float(ref mut lit) => lit.span = span,
+            Lit::Bool(ref mut lit) => lit.span = span,
+            Lit::Verbatim(ref mut lit) => lit.span = span,
+        }
+    }
+
+    /// The text of the token of this literal, such as `0xFF_u8` or
+    /// `r#"raw"#`.
+    ///
+    /// Literals keep the token they were created from, so for a literal that
+    /// was parsed this is its spelling in the original source, and printing
+    /// the literal with `ToTokens` reproduces that spelling exactly. For a
+    /// literal built by a constructor like `LitInt::new` it is whatever
+    /// spelling the constructor chose. A `Lit::Bool` is spelled `true` or
+    /// `false`.
+    pub fn original_spelling(&self) -> String {
+        match *self {
+            Lit::Str(ref lit) => lit.token.to_string(),
+            Lit::ByteStr(ref lit) => lit.token.to_string(),
+            Lit::Byte(ref lit) => lit.token.to_string(),
+            Lit::Char(ref lit) => lit.token.to_string(),
+            Lit::Int(ref lit) => lit.token.to_string(),
+            Lit::Float(ref lit) => lit.token.to_string(),
+            Lit::Bool(ref lit) => (if lit.value { "true" } else { "false" }).to_owned(),
+            Lit::Verbatim(ref lit) => lit.token.to_string(),
+        }
+    }
+
+    /// The length in bytes of the source text of this literal, for column
+    /// arithmetic on the original file.
+    ///
+    /// A `Lit::Bool` has no literal token; its length is that of the keyword,
+    /// 4 for `true` or 5 for `false`.
+    ///
+    /// This is also the length of `TokenStream::from(lit).to_string()`, so
+    /// summing it over a sequence of literals gives the offset at which each
+    /// one begins when they are emitted back to back.
+    pub fn source_byte_len(&self) -> usize {
+        self.original_spelling().len()
+    }
+
+    /// Whether the source text of this literal is pure ASCII.
+    ///
+    /// String and character literals may contain non-ASCII characters like
+    /// `é` written directly rather than as a `\u{e9}` escape. Code generated
+    /// for a toolchain that only accepts ASCII source files can use this to
+    /// find the literals that need to be re-emitted with escapes.
+    pub fn is_ascii_only_source(&self) -> bool {
+        self.original_spelling().bytes().all(|b| b < 0x80)
+    }
+
+    /// Returns a copy of this literal with its span replaced by `span`.
+    ///
+    /// This is the by-value counterpart of `set_span` and leaves `self`
+    /// untouched. It does not depend on the `"clone-impls"` feature.
+    pub fn respanned(&self, span: Span) -> Lit {
+        match *self {
+            Lit::Str(ref lit) => Lit::Str(LitStr {
+                token: lit.token.clone(),
+                span: span,
+            }),
+            Lit::ByteStr(ref lit) => Lit::ByteStr(LitByteStr {
+                token: lit.token.clone(),
+                span: span,
+            }),
+            Lit::Byte(ref lit) => Lit::Byte(LitByte {
+                token: lit.token.clone(),
+                span: span,
+            }),
+            Lit::Char(ref lit) => Lit::Char(LitChar {
+                token: lit.token.clone(),
+                span: span,
+            }),
+            Lit::Int(ref lit) => Lit::Int(LitInt {
+                token: lit.token.clone(),
+                repr: lit.repr.clone(),
+                span: span,
+            }),
+            Lit::Float(ref lit) => Lit::Float(LitFloat {
+                token: lit.token.clone(),
+                repr: lit.repr.clone(),
+                span: span,
+            }),
+            Lit::Bool(ref lit) => Lit::Bool(LitBool {
+                value: lit.value,
+                span: span,
+            }),
+            Lit::Verbatim(ref lit) => Lit::Verbatim(LitVerbatim {
+                token: lit.token.clone(),
+                span: span,
+            }),
+        }
+    }
+
+    /// Returns a copy of this literal with its numeric spelling made
+    /// canonical, without changing its value, suffix or span:
+    ///
+    /// - Redundant leading zeros are removed from decimal integers, so `007`
+    ///   becomes `7`.
+    /// - Hex digits are lowercased, so `0xFF` becomes `0xff`.
+    /// - A float ending in `.` gets a digit after it, so `1.` becomes `1.0`.
+    /// - An explicit `+` on a float exponent is removed, so `1e+5` becomes
+    ///   `1e5`.
+    ///
+    /// Underscores between digits are kept. Literals other than integers and
+    /// floats are returned unchanged.
+    pub fn normalized(&self) -> Lit {
+        match *self {
+            Lit::Int(ref lit) => {
+                let (digits, suffix) = value::split_suffix(&lit.repr);
+                let digits = if lit.has_leading_zero() {
+                    let trimmed = digits.trim_left_matches(|c| c == '0' || c == '_');
+                    if trimmed.is_empty() {
+                        "0".to_owned()
+                    } else {
+                        trimmed.to_owned()
+                    }
+                } else if lit.radix() == 16 {
+                    digits.to_lowercase()
+                } else {
+                    digits.to_owned()
+                };
+                Lit::Int(lit.respelled(&digits, suffix))
+            }
+            Lit::Float(ref lit) => {
+                let lit = lit.normalize_exponent(false);
+                if lit.has_trailing_dot() {
+                    Lit::Float(lit.respelled(&format!("{}0", lit.digits()), lit.suffix_str()))
+                } else {
+                    Lit::Float(lit)
+                }
+            }
+            _ => self.respanned(self.span()),
+        }
+    }
+
+    /// The literal as written in source, followed by `note` in a block
+    /// comment, like `255u8 /* width */`.
+    ///
+    /// This is for rendering generated code as text; comments are not tokens,
+    /// so the note cannot be carried in a token stream. Any `*/` in the note
+    /// is written as `* /` so that it cannot end the comment early, and any
+    /// `/*` as `/ *` so that it cannot open a nested comment that is never
+    /// closed.
+    pub fn to_source_annotated(&self, note: &str) -> String {
+        let note = note.replace("*/", "* /").replace("/*", "/ *");
+        format!("{} /* {} */", TokenStream::from(self), note)
+    }
+
+    /// Renders the value of this literal in a normalized form suitable for
+    /// quoting in a human-readable diagnostic.
+    ///
+    /// Strings and characters are shown with their content decoded and then
+    /// minimally re-escaped, as in `"\t"` or `'x'`. Numbers are shown as their
+    /// decoded value followed by any suffix, except that a float too large
+    /// for `f64` is shown as written.
+    ///
+    /// The result is always a valid Rust literal in which every backslash,
+    /// double quote and newline of the value is escaped, so it can also be
+    /// used to build generated code. For a string, the text between the outer
+    /// quotes can be embedded inside another string literal as is, without
+    /// the double escaping that comes from splicing in the source token.
+    pub fn display_escaped(&self) -> String {
+        match *self {
+            Lit::Str(ref lit) => format!("{:?}", lit.value()),
+            Lit::ByteStr(ref lit) => {
+                let mut repr = "b\"".to_owned();
+                for b in lit.value() {
+                    repr.extend(ascii::escape_default(b).map(char::from));
+                }
+                repr.push('"');
+                repr
+            }
+            Lit::Byte(ref lit) => {
+                let mut repr = "b'".to_owned();
+                repr.extend(ascii::escape_default(lit.value()).map(char::from));
+                repr.push('\'');
+                repr
+            }
+            Lit::Char(ref lit) => match lit.value() {
+                // Debug leaves a double quote unescaped inside a char.
+                '"' => "'\\\"'".to_owned(),
+                ch => format!("{:?}", ch),
+            },
+            Lit::Int(ref lit) => format!("{}{}", lit.value(), value::int_suffix_str(&lit.suffix())),
+            // A value out of range of f64 would print as `inf`, which is not a
+            // literal, so keep the source token instead.
+            Lit::Float(ref lit) if !lit.value().is_finite() => lit.token.to_string(),
+            Lit::Float(ref lit) => {
+                let suffix = value::float_suffix_str(&lit.suffix());
+                format!("{:?}{}", lit.value(), suffix)
+            }
+            Lit::Bool(ref lit) => lit.value.to_string(),
+            Lit::Verbatim(ref lit) => lit.token.to_string(),
+        }
+    }
+
+    /// Returns the bytes of a byte, byte string or string literal.
+    ///
+    /// A byte literal produces a single byte and a byte string its decoded
+    /// content. A string literal produces the UTF-8 encoding of its decoded
+    /// content, which is how the same text would be written as a byte string.
+    /// Returns `None` for all other kinds of literal.
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        match *self {
+            Lit::Str(ref lit) => Some(lit.value().into_bytes()),
+            Lit::ByteStr(ref lit) => Some(lit.value()),
+            Lit::Byte(ref lit) => Some(vec![lit.value()]),
+            _ => None,
+        }
+    }
 
-        /// An integer literal: `1` or `1u16`.
-        ///
-        /// Holds up to 64 bits of data. Use `LitVerbatim` for any larger
-        /// integer literal.
-        ///
-        /// *This type is available if Syn is built with the `"derive"` or
-        /// `"full"` feature.*
-        pub Int(LitInt #manual_extra_traits {
-            token: Literal,
-            pub span: Span,
-        }),
+    /// Converts a text or byte literal into a byte string with the given
+    /// span, or returns `None` for numeric, boolean and verbatim literals.
+    ///
+    /// Strings and chars are encoded as UTF-8, so `"é"` and `'é'` both become
+    /// `b"\xc3\xa9"`. Bytes and byte strings keep their bytes as they are.
+    pub fn coerce_to_byte_str(&self, span: Span) -> Option<LitByteStr> {
+        let bytes = match *self {
+            Lit::Char(ref lit) => lit.value().to_string().into_bytes(),
+            _ => match self.as_bytes() {
+                Some(bytes) => bytes,
+                None => return None,
+            },
+        };
+        Some(LitByteStr::new(&bytes, span))
+    }
 
-        /// A floating point literal: `1f64` or `1.0e10f64`.
-        ///
-        /// Must be finite. May not be infinte or NaN.
-        ///
-        /// *This type is available if Syn is built with the `"derive"` or
-        /// `"full"` feature.*
-        pub Float(LitFloat #manual_extra_traits {
-            token: Literal,
-            pub span: Span,
-        }),
+    /// Converts a text or byte literal into a string with the given span, or
+    /// returns `None` if there is no string with the same content.
+    ///
+    /// Strings and chars convert directly. Bytes and byte strings convert
+    /// only if their bytes are valid UTF-8, so `b"\xc3\xa9"` becomes `"é"`
+    /// but `b"\xff"` and `b'\x80'` give `None`. Numeric, boolean and verbatim
+    /// literals also give `None`.
+    pub fn coerce_to_str(&self, span: Span) -> Option<LitStr> {
+        let value = match *self {
+            Lit::Str(ref lit) => lit.value(),
+            Lit::Char(ref lit) => lit.value().to_string(),
+            Lit::ByteStr(ref lit) => match String::from_utf8(lit.value()) {
+                Ok(value) => value,
+                Err(_) => return None,
+            },
+            Lit::Byte(ref lit) => match String::from_utf8(vec![lit.value()]) {
+                Ok(value) => value,
+                Err(_) => return None,
+            },
+            _ => return None,
+        };
+        Some(LitStr::new(&value, span))
+    }
 
-        /// A boolean literal: `true` or `false`.
-        ///
-        /// *This type is available if Syn is built with the `"derive"` or
-        /// `"full"` feature.*
-        pub Bool(LitBool #manual_extra_traits {
-            pub value: bool,
-            pub span: Span,
-        }),
+    /// A borrow of the token this literal was created from, or `None` for a
+    /// `Lit::Bool`, which is a keyword rather than a literal token.
+    ///
+    /// Emitting a token through `ToTokens` always clones it because the
+    /// token stream takes ownership of its tokens. Callers that only need to
+    /// inspect the token, or that can emit it by some other means, can use
+    /// this to avoid the clone.
+    pub fn token_ref(&self) -> Option<&Literal> {
+        match *self {
+            Lit::Str(ref lit) => Some(&lit.token),
+            Lit::ByteStr(ref lit) => Some(&lit.token),
+            Lit::Byte(ref lit) => Some(&lit.token),
+            Lit::Char(ref lit) => Some(&lit.token),
+            Lit::Int(ref lit) => Some(&lit.token),
+            Lit::Float(ref lit) => Some(&lit.token),
+            Lit::Bool(_) => None,
+            Lit::Verbatim(ref lit) => Some(&lit.token),
+        }
+    }
 
-        /// A raw token literal not interpreted by Syn, possibly because it
-        /// represents an integer larger than 64 bits.
-        ///
-        /// *This type is available if Syn is built with the `"derive"` or
-        /// `"full"` feature.*
-        pub Verbatim(LitVerbatim #manual_extra_traits {
-            pub token: Literal,
-            pub span: Span,
-        }),
+    /// Whether this literal is the zero value of its type: `0` in any radix,
+    /// `0.0`, `b'\0'`, `'\0'` or `false`.
+    ///
+    /// A float literal is never negative, since a minus sign in the source is
+    /// a separate token, and `-0.0` compares equal to `0.0`, so a negative
+    /// zero float built by hand counts as zero too. Strings, byte strings and
+    /// verbatim literals are never zero.
+    #[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+    pub fn is_zero(&self) -> bool {
+        match *self {
+            Lit::Byte(ref lit) => lit.value() == 0,
+            Lit::Char(ref lit) => lit.value() == '\0',
+            Lit::Int(ref lit) => lit.value() == 0,
+            Lit::Float(ref lit) => lit.value() == 0.0,
+            Lit::Bool(ref lit) => !lit.value,
+            Lit::Str(_) | Lit::ByteStr(_) | Lit::Verbatim(_) => false,
+        }
     }
-}
 
-impl LitStr {
-    pub fn new(value: &str, span: Span) -> Self {
-        LitStr {
-            token: Literal::string(value),
-            span: span,
+    /// The length in bytes of the decoded value of a string or byte string
+    /// literal, counted without building the value. Returns `None` for other
+    /// kinds of literal.
+    ///
+    /// Together with `LitStr::value_limited` this lets a tool processing
+    /// untrusted input reject huge literals before decoding them.
+    pub fn decoded_len(&self) -> Option<usize> {
+        match *self {
+            Lit::Str(ref lit) => Some(lit.decoded_byte_len()),
+            Lit::ByteStr(ref lit) => Some(lit.bytes().count()),
+            _ => None,
         }
     }
 
-    pub fn value(&self) -> String {
-        value::parse_lit_str(&self.token.to_string())
+    /// Whether this is an integer or floating point literal.
+    pub fn is_numeric(&self) -> bool {
+        match *self {
+            Lit::Int(_) | Lit::Float(_) => true,
+            _ => false,
+        }
     }
-}
 
-impl LitByteStr {
-    pub fn new(value: &[u8], span: Span) -> Self {
-        LitByteStr {
-            token: Literal::byte_string(value),
-            span: span,
+    /// Whether this is a numeric literal with an explicit type suffix, like
+    /// `5u8` or `1.0f32`, as opposed to `5` or `1.0`.
+    ///
+    /// Always false for literals that are not numeric.
+    pub fn is_suffixed(&self) -> bool {
+        match *self {
+            Lit::Int(ref lit) => !lit.suffix_str().is_empty(),
+            Lit::Float(ref lit) => !lit.suffix_str().is_empty(),
+            _ => false,
         }
     }
 
-    pub fn value(&self) -> Vec<u8> {
-        value::parse_lit_byte_str(&self.token.to_string())
+    /// Checks that the literal decodes successfully, returning the error that
+    /// `value` would panic with if it does not.
+    pub fn validate(&self) -> Result<(), LitError> {
+        match *self {
+            Lit::Str(ref lit) => lit.validate(),
+            Lit::ByteStr(ref lit) => lit.validate(),
+            Lit::Byte(ref lit) => lit.validate(),
+            Lit::Char(ref lit) => lit.validate(),
+            Lit::Float(ref lit) => lit.try_value().map(|_| ()),
+            Lit::Int(_) | Lit::Bool(_) | Lit::Verbatim(_) => Ok(()),
+        }
     }
-}
 
-impl LitByte {
-    pub fn new(value: u8, span: Span) -> Self {
-        LitByte {
-            token: Literal::byte_char(value),
-            span: span,
+    /// Validates every literal in `tokens`, including those nested inside
+    /// groups, and returns all of the errors in the order the literals appear.
+    ///
+    /// Doc comments are skipped, and an empty vector means every literal is
+    /// well formed.
+    pub fn validate_all(tokens: &TokenStream) -> Vec<LitError> {
+        fn walk(tokens: &TokenStream, errors: &mut Vec<LitError>) {
+            for tt in tokens.clone() {
+                match tt.kind {
+                    TokenNode::Group(_, ref stream) => walk(stream, errors),
+                    TokenNode::Literal(ref token) => {
+                        if token.to_string().starts_with('/') {
+                            continue;
+                        }
+                        if let Err(err) = Lit::new(token.clone(), tt.span).validate() {
+                            errors.push(err);
+                        }
+                    }
+                    TokenNode::Term(_) | TokenNode::Op(..) => {}
+                }
+            }
         }
+
+        let mut errors = Vec::new();
+        walk(tokens, &mut errors);
+        errors
     }
 
-    pub fn value(&self) -> u8 {
-        value::parse_lit_byte(&self.token.to_string())
+    /// Which kind of literal this is.
+    pub fn kind(&self) -> LitKind {
+        match *self {
+            Lit::Str(_) => LitKind::Str,
+            Lit::ByteStr(_) => LitKind::ByteStr,
+            Lit::Byte(_) => LitKind::Byte,
+            Lit::Char(_) => LitKind::Char,
+            Lit::Int(_) => LitKind::Int,
+            Lit::Float(_) => LitKind::Float,
+            Lit::Bool(_) => LitKind::Bool,
+            Lit::Verbatim(_) => LitKind::Verbatim,
+        }
     }
-}
 
-impl LitChar {
-    pub fn new(value: char, span: Span) -> Self {
-        LitChar {
-            token: Literal::character(value),
-            span: span,
+    /// Whether two float literals have values within `epsilon` of each other,
+    /// or `None` if either literal is not a float.
+    ///
+    /// The suffixes are ignored, so `1.0f32` and `1.0` compare equal.
+    pub fn float_approx_eq(&self, other: &Lit, epsilon: f64) -> Option<bool> {
+        match (self, other) {
+            (&Lit::Float(ref a), &Lit::Float(ref b)) => {
+                Some((a.value() - b.value()).abs() <= epsilon)
+            }
+            _ => None,
         }
     }
 
-    pub fn value(&self) -> char {
-        value::parse_lit_char(&self.token.to_string())
+    /// A key that is equal for two literals exactly when they have the same
+    /// kind, decoded value and suffix, regardless of span or spelling.
+    ///
+    /// Keys order literals by kind first, like `cmp_value`. Integers are
+    /// ordered numerically within a kind, and an integer too large for a
+    /// `LitInt` gets the same key it would have as a `Lit::Int`.
+    pub fn canonical_key(&self) -> LitKey {
+        // Laid out like the big-endian bytes of a `u128`, the same as
+        // `value::int_be_bytes` gives for integers too large for a `LitInt`.
+        fn int_bytes(value: u64) -> Vec<u8> {
+            let mut bytes = vec![0; 8];
+            bytes.extend((0..8).rev().map(|i| (value >> (i * 8)) as u8));
+            bytes
+        }
+
+        let (kind, value, suffix) = match *self {
+            Lit::Str(ref lit) => (LitKind::Str, lit.value().into_bytes(), ""),
+            Lit::ByteStr(ref lit) => (LitKind::ByteStr, lit.value(), ""),
+            Lit::Byte(ref lit) => (LitKind::Byte, vec![lit.value()], ""),
+            Lit::Char(ref lit) => (LitKind::Char, lit.value().to_string().into_bytes(), ""),
+            Lit::Int(ref lit) => (LitKind::Int, int_bytes(lit.value()), lit.suffix_str()),
+            Lit::Float(ref lit) => {
+                (LitKind::Float, lit.value().to_string().into_bytes(), lit.suffix_str())
+            }
+            Lit::Bool(ref lit) => (LitKind::Bool, vec![lit.value as u8], ""),
+            Lit::Verbatim(ref lit) => {
+                let repr = lit.token.to_string();
+                return match value::int_be_bytes(&repr) {
+                    Some(bytes) => LitKey {
+                        kind: LitKind::Int,
+                        value: bytes,
+                        suffix: value::split_suffix(&repr).1.to_owned(),
+                    },
+                    None => LitKey {
+                        kind: LitKind::Verbatim,
+                        value: repr.into_bytes(),
+                        suffix: String::new(),
+                    },
+                };
+            }
+        };
+        LitKey {
+            kind: kind,
+            value: value,
+            suffix: suffix.to_owned(),
+        }
     }
-}
 
-impl LitInt {
-    pub fn new(value: u64, suffix: IntSuffix, span: Span) -> Self {
-        LitInt {
-            token: match suffix {
-                IntSuffix::Isize => Literal::isize(value as isize),
-                IntSuffix::I8 => Literal::i8(value as i8),
-                IntSuffix::I16 => Literal::i16(value as i16),
-                IntSuffix::I32 => Literal::i32(value as i32),
-                IntSuffix::I64 => Literal::i64(value as i64),
-                IntSuffix::I128 => value::to_literal(&format!("{}i128", value)),
-                IntSuffix::Usize => Literal::usize(value as usize),
-                IntSuffix::U8 => Literal::u8(value as u8),
-                IntSuffix::U16 => Literal::u16(value as u16),
-                IntSuffix::U32 => Literal::u32(value as u32),
-                IntSuffix::U64 => Literal::u64(value),
-                IntSuffix::U128 => value::to_literal(&format!("{}u128", value)),
-                IntSuffix::None => Literal::integer(value as i64),
-            },
-            span: span,
+    /// A 64-bit hash of the kind, decoded value and suffix of this literal,
+    /// the same parts that make up `canonical_key`, so `"a"` and `r"a"` have
+    /// the same fingerprint while `1u8` and `1u16` do not.
+    ///
+    /// The hash is FNV-1a rather than the standard library's hasher, whose
+    /// algorithm may change between Rust releases, so a fingerprint stays the
+    /// same across runs and builds and can key a persistent cache.
+    pub fn fingerprint(&self) -> u64 {
+        fn write(hash: &mut u64, bytes: &[u8]) {
+            for &b in bytes {
+                *hash ^= u64::from(b);
+                *hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+
+        let key = self.canonical_key();
+        let kind = match key.kind {
+            LitKind::Str => 0,
+            LitKind::ByteStr => 1,
+            LitKind::Byte => 2,
+            LitKind::Char => 3,
+            LitKind::Int => 4,
+            LitKind::Float => 5,
+            LitKind::Bool => 6,
+            LitKind::Verbatim => 7,
+        };
+
+        let mut hash = 0xcbf2_9ce4_8422_2325;
+        write(&mut hash, &[kind]);
+        // Lengths keep the boundary between value and suffix unambiguous.
+        for part in &[&key.value[..], key.suffix.as_bytes()] {
+            let len = part.len() as u64;
+            write(&mut hash, &[len as u8, (len >> 8) as u8, (len >> 16) as u8, (len >> 24) as u8]);
+            write(&mut hash, part);
         }
+        hash
     }
 
-    pub fn value(&self) -> u64 {
-        value::parse_lit_int(&self.token.to_string()).unwrap()
+    /// Sorts `lits` by `canonical_key` and removes all but the first of each
+    /// run of literals with equal keys.
+    ///
+    /// The sort is stable, so of several literals with the same value the one
+    /// that appeared first in `lits` is kept along with its span and spelling.
+    pub fn dedup(lits: &mut Vec<Lit>) {
+        let mut keyed: Vec<(LitKey, Lit)> =
+            lits.drain(..).map(|lit| (lit.canonical_key(), lit)).collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut last = None;
+        for (key, lit) in keyed {
+            if last.as_ref() != Some(&key) {
+                lits.push(lit);
+                last = Some(key);
+            }
+        }
     }
 
-    pub fn suffix(&self) -> IntSuffix {
-        let value = self.token.to_string();
-        for (s, suffix) in vec![
-            ("i8", IntSuffix::I8),
-            ("i16", IntSuffix::I16),
-            ("i32", IntSuffix::I32),
-            ("i64", IntSuffix::I64),
-            ("i128", IntSuffix::I128),
-            ("isize", IntSuffix::Isize),
-            ("u8", IntSuffix::U8),
-            ("u16", IntSuffix::U16),
-            ("u32", IntSuffix::U32),
-            ("u64", IntSuffix::U64),
-            ("u128", IntSuffix::U128),
-            ("usize", IntSuffix::Usize),
-        ] {
-            if value.ends_with(s) {
-                return suffix;
+    /// A total order on literals for sorting them deterministically.
+    ///
+    /// Literals are ordered first by their `LitKind`, in the order the kinds
+    /// are declared. Within a kind they are ordered by decoded value: numbers
+    /// and booleans numerically, strings, byte strings and characters
+    /// lexicographically, and verbatim literals by their token text. Literals
+    /// with equal values but different spellings, like `1` and `1u8` or `"a"`
+    /// and `r"a"`, are ordered by their spelling so that no two distinct
+    /// tokens compare equal.
+    pub fn cmp_value(&self, other: &Lit) -> Ordering {
+        let by_value = match (self, other) {
+            (&Lit::Str(ref a), &Lit::Str(ref b)) => a.value().cmp(&b.value()),
+            (&Lit::ByteStr(ref a), &Lit::ByteStr(ref b)) => a.value().cmp(&b.value()),
+            (&Lit::Byte(ref a), &Lit::Byte(ref b)) => a.value().cmp(&b.value()),
+            (&Lit::Char(ref a), &Lit::Char(ref b)) => a.value().cmp(&b.value()),
+            (&Lit::Int(ref a), &Lit::Int(ref b)) => a.value().cmp(&b.value()),
+            (&Lit::Float(ref a), &Lit::Float(ref b)) => {
+                // Float literals are always finite, so never NaN.
+                a.value().partial_cmp(&b.value()).unwrap_or(Ordering::Equal)
+            }
+            (&Lit::Bool(ref a), &Lit::Bool(ref b)) => a.value.cmp(&b.value),
+            (&Lit::Verbatim(ref a), &Lit::Verbatim(ref b)) => {
+                a.token.to_string().cmp(&b.token.to_string())
             }
+            _ => return self.kind().cmp(&other.kind()),
+        };
+        match by_value {
+            Ordering::Equal => self.original_spelling().cmp(&other.original_spelling()),
+            ordering => ordering,
         }
-        IntSuffix::None
     }
 }
 
-impl LitFloat {
-    pub fn new(value: f64, suffix: FloatSuffix, span: Span) -> Self {
-        LitFloat {
-            token: match suffix {
-                FloatSuffix::F32 => Literal::f32(value as f32),
-                FloatSuffix::F64 => Literal::f64(value),
-                FloatSuffix::None => Literal::float(value),
+/// A numeric literal together with a preceding minus sign, if any, as
+/// returned by [`Lit::parse_maybe_negative`] and [`Lit::from_i64`].
+///
+/// Printing it emits the `-` as its own token followed by the literal, which
+/// `parse_maybe_negative` parses back.
+///
+/// [`Lit::parse_maybe_negative`]: enum.Lit.html#method.parse_maybe_negative
+/// [`Lit::from_i64`]: enum.Lit.html#method.from_i64
+///
+/// *This type is available if Syn is built with the `"derive"` or `"full"`
+/// feature.*
+#[cfg_attr(feature = "extra-traits", derive(Debug, Eq, PartialEq, Hash))]
+#[cfg_attr(feature = "clone-impls", derive(Clone))]
+pub struct SignedLit {
+    /// Whether the literal was preceded by a `-`.
+    pub negative: bool,
+    pub lit: Lit,
+}
+
+impl SignedLit {
+    /// The value of an integer literal with the sign applied, or `None` if
+    /// the literal is not an integer or the value does not fit in an `i128`.
+    ///
+    /// Integers too large for a `LitInt`, which are represented as
+    /// `Lit::Verbatim`, are supported too.
+    ///
+    /// *This method is available if Syn is built with the `"i128"` feature.*
+    #[cfg(feature = "i128")]
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = match self.lit {
+            Lit::Int(ref lit) => u128::from(lit.value()),
+            Lit::Verbatim(ref lit) => match value::parse_int_u128(&lit.token.to_string()) {
+                Some(magnitude) => magnitude,
+                None => return None,
             },
-            span: span,
+            _ => return None,
+        };
+        if !self.negative {
+            if magnitude <= i128::max_value() as u128 {
+                Some(magnitude as i128)
+            } else {
+                None
+            }
+        } else if magnitude <= i128::max_value() as u128 {
+            Some(-(magnitude as i128))
+        } else if magnitude == i128::max_value() as u128 + 1 {
+            Some(i128::min_value())
+        } else {
+            None
         }
     }
+}
 
-    pub fn value(&self) -> f64 {
-        value::parse_lit_float(&self.token.to_string())
-    }
+/// A scalar value of a JSON document, for converting to a literal with
+/// [`Lit::from_json_scalar`] without depending on a JSON library.
+///
+/// [`Lit::from_json_scalar`]: enum.Lit.html#method.from_json_scalar
+///
+/// *This type is available if Syn is built with the `"derive"` or `"full"`
+/// feature.*
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq))]
+#[cfg_attr(feature = "clone-impls", derive(Clone))]
+pub enum Scalar {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
 
-    pub fn suffix(&self) -> FloatSuffix {
-        let value = self.token.to_string();
-        for (s, suffix) in vec![("f32", FloatSuffix::F32), ("f64", FloatSuffix::F64)] {
-            if value.ends_with(s) {
-                return suffix;
+/// The kind of a literal, without its value.
+///
+/// The variants correspond one to one with those of [`Lit`] and are ordered
+/// the same way.
+///
+/// [`Lit`]: enum.Lit.html
+///
+/// *This type is available if Syn is built with the `"derive"` or `"full"`
+/// feature.*
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub enum LitKind {
+    Str,
+    ByteStr,
+    Byte,
+    Char,
+    Int,
+    Float,
+    Bool,
+    Verbatim,
+}
+
+/// A key identifying a literal by its kind, decoded value and suffix, as
+/// returned by [`Lit::canonical_key`].
+///
+/// [`Lit::canonical_key`]: enum.Lit.html#method.canonical_key
+///
+/// The span and spelling of the literal are not part of the key, so `0xFF` and
+/// `255` have equal keys while `255u8` and `255u16` do not.
+///
+/// *This type is available if Syn is built with the `"derive"` or `"full"`
+/// feature.*
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub struct LitKey {
+    kind: LitKind,
+    value: Vec<u8>,
+    suffix: String,
+}
+
+impl Lit {
+    fn token_node(&self) -> TokenNode {
+        match *self {
+            Lit::Str(ref lit) => TokenNode::Literal(lit.token.clone()),
+            Lit::ByteStr(ref lit) => TokenNode::Literal(lit.token.clone()),
+            Lit::Byte(ref lit) => TokenNode::Literal(lit.token.clone()),
+            Lit::Char(ref lit) => TokenNode::Literal(lit.token.clone()),
+            Lit::Int(ref lit) => TokenNode::Literal(lit.token.clone()),
+            Lit::Float(ref lit) => TokenNode::Literal(lit.token.clone()),
+            Lit::Bool(ref lit) => {
+                TokenNode::Term(Term::intern(if lit.value { "true" } else { "false" }))
             }
+            Lit::Verbatim(ref lit) => TokenNode::Literal(lit.token.clone()),
         }
-        FloatSuffix::None
+    }
+}
+
+impl<'a> From<&'a Lit> for TokenStream {
+    fn from(lit: &'a Lit) -> Self {
+        TokenStream::from(TokenTree {
+            span: lit.span(),
+            kind: lit.token_node(),
+        })
+    }
+}
+
+impl From<Lit> for TokenStream {
+    fn from(lit: Lit) -> Self {
+        TokenStream::from(&lit)
     }
 }
 
@@ -320,10 +2462,27 @@ ast_enum! {
     }
 }
 
+impl IntSuffix {
+    /// Maps the name of an integer type, like the `u64` in a DSL's `5 u64`,
+    /// to its suffix, or to `IntSuffix::None` if it is not one.
+    pub fn from_ident(ident: &str) -> IntSuffix {
+        value::int_suffix_from_str(ident)
+    }
+}
+
+impl FloatSuffix {
+    /// Maps the name of a float type, like the `f32` in a DSL's `1.5 f32`,
+    /// to its suffix, or to `FloatSuffix::None` if it is not one.
+    pub fn from_ident(ident: &str) -> FloatSuffix {
+        value::float_suffix_from_str(ident)
+    }
+}
+
 #[cfg(feature = "parsing")]
 pub mod parsing {
     use super::*;
-    use synom::Synom;
+    use proc_macro2::Delimiter;
+    use synom::{ParseError, Synom};
     use buffer::Cursor;
     use parse_error;
     use synom::PResult;
@@ -340,7 +2499,14 @@ pub mod parsing {
                             } else if term.as_str() == "false" {
                                 false
                             } else {
-                                return parse_error();
+                                return match bool_suggestion(term.as_str()) {
+                                    Some(suggestion) => Err(ParseError::new(format!(
+                                        "`{}` is not a boolean literal; use `{}`",
+                                        term.as_str(),
+                                        suggestion
+                                    ))),
+                                    None => parse_error(),
+                                };
                             },
                             span: span,
                         }),
@@ -356,6 +2522,232 @@ pub mod parsing {
         }
     }
 
+    /// The boolean literal meant by a term that is commonly written in place of
+    /// one, like `yes` or `False`.
+    fn bool_suggestion(term: &str) -> Option<&'static str> {
+        match term {
+            "on" | "yes" | "True" | "TRUE" => Some("true"),
+            "off" | "no" | "False" | "FALSE" => Some("false"),
+            _ => None,
+        }
+    }
+
+    impl Lit {
+        /// Parses an `=` followed by a literal, like the `= "value"` in
+        /// `#[attr(key = "value")]`, and returns the literal.
+        pub fn parse_eq_value(input: Cursor) -> PResult<Self> {
+            let rest = match <Token![=]>::parse(input) {
+                Ok((_, rest)) => rest,
+                Err(_) => return Err(ParseError::new("expected `=` before literal")),
+            };
+            match Lit::parse(rest) {
+                Ok(ok) => Ok(ok),
+                Err(_) => Err(ParseError::new("expected literal after `=`")),
+            }
+        }
+    }
+
+    impl Lit {
+        /// Parses a single literal from source text, also returning the
+        /// whitespace and comments that precede it.
+        ///
+        /// Tokens do not carry the trivia around them, so a formatter that
+        /// wants to reproduce the original layout has to work from the source
+        /// text instead. For `"  /* c */ 42"` this returns the integer `42`
+        /// along with the trivia `"  /* c */ "`.
+        ///
+        /// Doc comments are not trivia; like any other text that is not a
+        /// single literal, they are reported as an error.
+        ///
+        /// *This method is available if Syn is built with the `"parsing"`
+        /// feature.*
+        pub fn parse_str_with_trivia(src: &str) -> Result<(Lit, String), LitError> {
+            let mut rest = src;
+            loop {
+                rest = rest.trim_left();
+                match comment_len(rest)? {
+                    Some(len) => rest = &rest[len..],
+                    None => break,
+                }
+            }
+            let trivia = &src[..src.len() - rest.len()];
+            Lit::parse_str(rest).map(|lit| (lit, trivia.to_owned()))
+        }
+    }
+
+    /// The length of the ordinary comment at the start of `s`, if there is
+    /// one. Doc comments are tokens, so they are left alone.
+    fn comment_len(s: &str) -> Result<Option<usize>, LitError> {
+        if s.starts_with("//") {
+            let inner = &s[2..];
+            if inner.starts_with('!') || (inner.starts_with('/') && !inner.starts_with("//")) {
+                return Ok(None);
+            }
+            return Ok(Some(s.find('\n').unwrap_or(s.len())));
+        }
+        if s.starts_with("/*") {
+            let inner = &s[2..];
+            if inner.starts_with('!')
+                || (inner.starts_with('*') && !inner.starts_with("**") && !inner.starts_with("*/"))
+            {
+                return Ok(None);
+            }
+            // Block comments nest.
+            let bytes = s.as_bytes();
+            let mut depth = 0;
+            let mut i = 0;
+            while i + 1 < bytes.len() {
+                match (bytes[i], bytes[i + 1]) {
+                    (b'/', b'*') => {
+                        depth += 1;
+                        i += 2;
+                    }
+                    (b'*', b'/') => {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            return Ok(Some(i));
+                        }
+                    }
+                    _ => i += 1,
+                }
+            }
+            return Err(LitError::new(
+                Span::call_site(),
+                "unterminated block comment".to_owned(),
+            ));
+        }
+        Ok(None)
+    }
+
+    impl Lit {
+        /// Parses a literal that may be wrapped in one level of parentheses, so
+        /// that `42` and `(42)` both give the integer `42`.
+        ///
+        /// The parentheses must contain exactly one literal.
+        ///
+        /// *This method is available if Syn is built with the `"parsing"`
+        /// feature.*
+        pub fn parse_maybe_parenthesized(input: Cursor) -> PResult<Lit> {
+            match input.group(Delimiter::Parenthesis) {
+                Some((inside, _, rest)) => {
+                    let (lit, after) = Lit::parse(inside)?;
+                    if after.eof() {
+                        Ok((lit, rest))
+                    } else {
+                        Err(ParseError::new("expected a single literal inside parentheses"))
+                    }
+                }
+                None => Lit::parse(input),
+            }
+        }
+
+        /// Parses source text consisting of exactly one literal, or `true` or
+        /// `false`, without panicking on malformed input.
+        ///
+        /// The error has the span of the offending token, or the call site span
+        /// if the text is empty or does not lex.
+        ///
+        /// *This method is available if Syn is built with the `"parsing"`
+        /// feature.*
+        pub fn parse_str(s: &str) -> Result<Lit, LitError> {
+            let error = |span: Span, message: String| Err(LitError::new(span, message));
+            let stream = match s.parse::<TokenStream>() {
+                Ok(stream) => stream,
+                Err(_) => {
+                    return error(Span::call_site(), "error while lexing literal".to_owned())
+                }
+            };
+            let mut tokens = stream.into_iter();
+            let token = match tokens.next() {
+                Some(token) => token,
+                None => return error(Span::call_site(), "expected literal".to_owned()),
+            };
+            let lit = match token.kind {
+                // Doc comments lex as literals but are not literals.
+                TokenNode::Literal(ref lit) if lit.to_string().starts_with('/') => {
+                    return error(token.span, "expected literal, found doc comment".to_owned())
+                }
+                TokenNode::Literal(lit) => Lit::new(lit, token.span),
+                TokenNode::Term(term) => match term.as_str() {
+                    "true" => Lit::Bool(LitBool {
+                        value: true,
+                        span: token.span,
+                    }),
+                    "false" => Lit::Bool(LitBool {
+                        value: false,
+                        span: token.span,
+                    }),
+                    other => {
+                        return match bool_suggestion(other) {
+                            Some(suggestion) => error(
+                                token.span,
+                                format!(
+                                    "`{}` is not a boolean literal; use `{}`",
+                                    other, suggestion
+                                ),
+                            ),
+                            None => {
+                                error(token.span, format!("expected literal, found `{}`", other))
+                            }
+                        }
+                    }
+                },
+                TokenNode::Op(op, _) => {
+                    return error(token.span, format!("`{}` is not a literal", op))
+                }
+                TokenNode::Group(..) => return error(token.span, "expected literal".to_owned()),
+            };
+            match tokens.next() {
+                Some(extra) => {
+                    error(extra.span, format!("unexpected token `{}` after literal", extra))
+                }
+                None => Ok(lit),
+            }
+        }
+    }
+
+    fn peek_kind(input: Cursor) -> Option<LitKind> {
+        if let Some((span, lit, _)) = input.literal() {
+            return Some(Lit::new(lit, span).kind());
+        }
+        match input.term() {
+            Some((_, term, _)) if term.as_str() == "true" || term.as_str() == "false" => {
+                Some(LitKind::Bool)
+            }
+            _ => None,
+        }
+    }
+
+    impl Lit {
+        /// Whether the next token is a literal of any kind, without consuming
+        /// it.
+        pub fn peek(input: Cursor) -> bool {
+            peek_kind(input).is_some()
+        }
+    }
+
+    // Each generated `peek` checks the next token without consuming it.
+    macro_rules! impl_peek {
+        ($ty:ident $kind:ident $doc:expr) => {
+            impl $ty {
+                #[doc = $doc]
+                pub fn peek(input: Cursor) -> bool {
+                    peek_kind(input) == Some(LitKind::$kind)
+                }
+            }
+        };
+    }
+
+    impl_peek!(LitStr Str "Whether the next token is a string literal.");
+    impl_peek!(LitByteStr ByteStr "Whether the next token is a byte string literal.");
+    impl_peek!(LitByte Byte "Whether the next token is a byte literal.");
+    impl_peek!(LitChar Char "Whether the next token is a character literal.");
+    impl_peek!(LitInt Int "Whether the next token is an integer literal.");
+    impl_peek!(LitFloat Float "Whether the next token is a floating point literal.");
+    impl_peek!(LitBool Bool "Whether the next token is a boolean literal.");
+    impl_peek!(LitVerbatim Verbatim "Whether the next token is a verbatim literal.");
+
     impl_synom!(LitStr "string literal" switch!(
         syn!(Lit),
         Lit::Str(lit) => value!(lit)
@@ -398,19 +2790,129 @@ pub mod parsing {
         _ => reject!()
     ));
 
-    impl_synom!(LitBool "boolean literal" switch!(
-        syn!(Lit),
-        Lit::Bool(lit) => value!(lit)
-        |
-        _ => reject!()
-    ));
+    impl_synom!(LitBool "boolean literal" switch!(
+        syn!(Lit),
+        Lit::Bool(lit) => value!(lit)
+        |
+        _ => reject!()
+    ));
+
+    impl Lit {
+        /// Parses a literal optionally preceded by a `-`, like the arguments in
+        /// `#[range(-5, 10)]`.
+        ///
+        /// A literal after a `-` must be numeric, which includes integers too
+        /// large for a `LitInt`. Without a `-`, any literal is accepted.
+        pub fn parse_maybe_negative(input: Cursor) -> PResult<SignedLit> {
+            let (negative, rest) = match <Token![-]>::parse(input) {
+                Ok((_, rest)) => (true, rest),
+                Err(_) => (false, input),
+            };
+            let (lit, rest) = match Lit::parse(rest) {
+                Ok(ok) => ok,
+                Err(_) if negative => {
+                    return Err(ParseError::new("expected number after `-`"))
+                }
+                Err(err) => return Err(err),
+            };
+            if negative && !is_numeric_token(&lit) {
+                return Err(ParseError::new("expected number after `-`"));
+            }
+            let signed = SignedLit {
+                negative: negative,
+                lit: lit,
+            };
+            Ok((signed, rest))
+        }
+
+        /// Parses a literal optionally preceded by a `+`, which is dropped,
+        /// for DSLs that let users write an explicit plus sign as in `+5`.
+        ///
+        /// In Rust the `+` is a separate token rather than part of the
+        /// literal, and this is not accepted by the `Synom` impl of `Lit`. As
+        /// with `parse_maybe_negative`, a literal after a `+` must be numeric,
+        /// while without a `+` any literal is accepted.
+        pub fn parse_maybe_plus(input: Cursor) -> PResult<Lit> {
+            let (plus, rest) = match <Token![+]>::parse(input) {
+                Ok((_, rest)) => (true, rest),
+                Err(_) => (false, input),
+            };
+            let (lit, rest) = match Lit::parse(rest) {
+                Ok(ok) => ok,
+                Err(_) if plus => return Err(ParseError::new("expected number after `+`")),
+                Err(err) => return Err(err),
+            };
+            if plus && !is_numeric_token(&lit) {
+                return Err(ParseError::new("expected number after `+`"));
+            }
+            Ok((lit, rest))
+        }
+    }
+
+    /// Whether the literal token is a number. Unlike `Lit::is_numeric` this
+    /// includes a `Lit::Verbatim` holding an integer too large for a `LitInt`.
+    fn is_numeric_token(lit: &Lit) -> bool {
+        match *lit {
+            Lit::Int(_) | Lit::Float(_) => true,
+            Lit::Verbatim(ref lit) => value::is_digit(value::byte(&lit.token.to_string(), 0)),
+            _ => false,
+        }
+    }
+
+    impl LitInt {
+        /// Parses an integer literal optionally followed by a type ascription,
+        /// like `5: u8`, folding the ascribed type into the suffix of the
+        /// returned literal as if it had been written `5u8`.
+        ///
+        /// This is not Rust syntax and is not accepted by the `Synom` impl of
+        /// `LitInt`; it is for macros that want to offer the sugar in their own
+        /// DSL.
+        pub fn parse_ascribed(input: Cursor) -> PResult<Self> {
+            let (lit, rest) = LitInt::parse(input)?;
+            let rest = match <Token![:]>::parse(rest) {
+                Ok((_, rest)) => rest,
+                Err(_) => return Ok((lit, rest)),
+            };
+            let (term, rest) = match rest.term() {
+                Some((_, term, rest)) => (term, rest),
+                None => return Err(ParseError::new("expected integer type after `:`")),
+            };
+            let ty = term.as_str();
+            if let IntSuffix::None = value::int_suffix_from_str(ty) {
+                return Err(ParseError::new(format!(
+                    "expected integer type after `:`, found `{}`",
+                    ty
+                )));
+            }
+            if !lit.suffix_str().is_empty() {
+                return Err(ParseError::new(format!(
+                    "integer literal `{}` already has a suffix",
+                    lit.token
+                )));
+            }
+            let token = value::to_literal(&format!("{}{}", lit.token, ty));
+            Ok((LitInt::from_token(token, lit.span), rest))
+        }
+    }
 }
 
 #[cfg(feature = "printing")]
 mod printing {
     use super::*;
+    use proc_macro2::Spacing;
     use quote::{ToTokens, Tokens};
 
+    impl Lit {
+        /// Emits the literal like `to_tokens`, but with `span` in place of the
+        /// span stored in the literal.
+        pub fn to_tokens_spanned(&self, tokens: &mut Tokens, span: Span) {
+            tokens.append(TokenTree {
+                span: span,
+                kind: self.token_node(),
+            });
+        }
+    }
+
     impl ToTokens for LitStr {
         fn to_tokens(&self, tokens: &mut Tokens) {
             tokens.append(TokenTree {
@@ -482,54 +2984,207 @@ mod printing {
             });
         }
     }
+
+    impl ToTokens for SignedLit {
+        fn to_tokens(&self, tokens: &mut Tokens) {
+            if self.negative {
+                tokens.append(TokenTree {
+                    span: self.lit.span(),
+                    kind: TokenNode::Op('-', Spacing::Alone),
+                });
+            }
+            self.lit.to_tokens(tokens);
+        }
+    }
+}
+
+#[cfg(feature = "lit-encoding")]
+mod encoding {
+    use super::*;
+    use std::str::FromStr;
+
+    // Each literal is encoded as one byte for its kind, the length of its
+    // token as a little-endian u32, and the token text. Spans are not encoded.
+    const STR: u8 = 0;
+    const BYTE_STR: u8 = 1;
+    const BYTE: u8 = 2;
+    const CHAR: u8 = 3;
+    const INT: u8 = 4;
+    const FLOAT: u8 = 5;
+    const BOOL: u8 = 6;
+    const VERBATIM: u8 = 7;
+
+    impl Lit {
+        /// Appends a compact binary encoding of this literal to `out`, for
+        /// caching parsed literals without going through text.
+        ///
+        /// The encoding is one byte for the kind of literal, the length of
+        /// the token text as a little-endian `u32`, and the token text. The
+        /// span is not encoded.
+        ///
+        /// *This method is available if Syn is built with the `"lit-encoding"`
+        /// feature.*
+        pub fn encode(&self, out: &mut Vec<u8>) {
+            let kind = match *self {
+                Lit::Str(_) => STR,
+                Lit::ByteStr(_) => BYTE_STR,
+                Lit::Byte(_) => BYTE,
+                Lit::Char(_) => CHAR,
+                Lit::Int(_) => INT,
+                Lit::Float(_) => FLOAT,
+                Lit::Bool(_) => BOOL,
+                Lit::Verbatim(_) => VERBATIM,
+            };
+            let token = TokenStream::from(self).to_string();
+            let len = token.len() as u32;
+            out.push(kind);
+            for shift in &[0, 8, 16, 24] {
+                out.push((len >> shift) as u8);
+            }
+            out.extend_from_slice(token.as_bytes());
+        }
+
+        /// Decodes a literal written by `encode` from the front of `bytes`
+        /// and advances `bytes` past it. The literal is given the call site
+        /// span.
+        ///
+        /// Any input that is not a literal written by `encode` is an error,
+        /// never a panic, and leaves `bytes` where it was.
+        ///
+        /// *This method is available if Syn is built with the `"lit-encoding"`
+        /// feature.*
+        pub fn decode(bytes: &mut &[u8]) -> Result<Lit, LitError> {
+            let span = Span::call_site();
+            let error = |message: String| Err(LitError::new(span, message));
+
+            if bytes.len() < 5 {
+                return error("unexpected end of input while decoding literal".to_owned());
+            }
+            let kind = bytes[0];
+            let len = bytes[1..5]
+                .iter()
+                .rev()
+                .fold(0usize, |len, &b| len << 8 | b as usize);
+            if bytes.len() - 5 < len {
+                return error("unexpected end of input while decoding literal".to_owned());
+            }
+            let token = match str::from_utf8(&bytes[5..5 + len]) {
+                Ok(token) => token,
+                Err(_) => return error("encoded literal is not valid UTF-8".to_owned()),
+            };
+
+            let lit = match decode_token(token, span) {
+                Some(lit) => lit,
+                None => return error(format!("invalid literal token `{}`", token)),
+            };
+            let expected = match kind {
+                STR => LitKind::Str,
+                BYTE_STR => LitKind::ByteStr,
+                BYTE => LitKind::Byte,
+                CHAR => LitKind::Char,
+                INT => LitKind::Int,
+                FLOAT => LitKind::Float,
+                BOOL => LitKind::Bool,
+                VERBATIM => LitKind::Verbatim,
+                _ => return error(format!("unknown literal kind {}", kind)),
+            };
+            if lit.kind() != expected {
+                return error(format!("literal `{}` is not of the encoded kind", token));
+            }
+
+            *bytes = &bytes[5 + len..];
+            Ok(lit)
+        }
+    }
+
+    fn decode_token(token: &str, span: Span) -> Option<Lit> {
+        let mut trees = match TokenStream::from_str(token) {
+            Ok(stream) => stream.into_iter(),
+            Err(_) => return None,
+        };
+        let tree = match (trees.next(), trees.next()) {
+            (Some(tree), None) => tree,
+            _ => return None,
+        };
+        match tree.kind {
+            // Doc comments are literal tokens too but not ones Lit::new
+            // accepts.
+            TokenNode::Literal(ref literal) => match value::byte(&literal.to_string(), 0) {
+                b'"' | b'\'' | b'b' | b'r' | b'0'...b'9' => Some(Lit::new(literal.clone(), span)),
+                _ => None,
+            },
+            TokenNode::Term(term) => match term.as_str() {
+                "true" => Some(Lit::Bool(LitBool {
+                    value: true,
+                    span: span,
+                })),
+                "false" => Some(Lit::Bool(LitBool {
+                    value: false,
+                    span: span,
+                })),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 mod value {
     use super::*;
     use std::char;
     use std::ops::{Index, RangeFrom};
-    use proc_macro2::TokenStream;
+    use unicode_xid::UnicodeXID;
 
     impl Lit {
         pub fn new(token: Literal, span: Span) -> Self {
             let value = token.to_string();
+            let (first, second) = (value::byte(&value, 0), value::byte(&value, 1));
+
+            // A lone digit is the most common literal of all and is always an
+            // unsuffixed decimal integer, so skip the number classification.
+            if value.len() == 1 && value::is_digit(first) {
+                return Lit::Int(LitInt {
+                    token: token,
+                    repr: value,
+                    span: span,
+                });
+            }
 
-            match value::byte(&value, 0) {
-                b'"' | b'r' => {
+            match (first, second) {
+                (b'"', _) | (b'r', _) => {
                     return Lit::Str(LitStr {
                         token: token,
                         span: span,
                     })
                 }
-                b'b' => match value::byte(&value, 1) {
-                    b'"' | b'r' => {
-                        return Lit::ByteStr(LitByteStr {
-                            token: token,
-                            span: span,
-                        })
-                    }
-                    b'\'' => {
-                        return Lit::Byte(LitByte {
-                            token: token,
-                            span: span,
-                        })
-                    }
-                    _ => {}
-                },
-                b'\'' => {
+                (b'b', b'"') | (b'b', b'r') => {
+                    return Lit::ByteStr(LitByteStr {
+                        token: token,
+                        span: span,
+                    })
+                }
+                (b'b', b'\'') => {
+                    return Lit::Byte(LitByte {
+                        token: token,
+                        span: span,
+                    })
+                }
+                (b'\'', _) => {
                     return Lit::Char(LitChar {
                         token: token,
                         span: span,
                     })
                 }
-                b'0'...b'9' => if number_is_int(&value) {
+                (b'0'...b'9', _) => if number_is_int(&value) {
                     return Lit::Int(LitInt {
                         token: token,
+                        repr: value,
                         span: span,
                     });
                 } else if number_is_float(&value) {
                     return Lit::Float(LitFloat {
                         token: token,
+                        repr: value,
                         span: span,
                     });
                 } else {
@@ -552,20 +3207,70 @@ mod value {
     }
 
     fn number_is_int(value: &str) -> bool {
+        number_is_int_of_any_size(value) && value::parse_lit_int(value).is_some()
+    }
+
+    /// Like `number_is_int`, but also true for integers that do not fit in a
+    /// `u64`.
+    fn number_is_int_of_any_size(value: &str) -> bool {
         if number_is_float(value) {
             false
         } else {
-            value::parse_lit_int(value).is_some()
+            let (digits, suffix) = split_int_suffix(value);
+            int_suffix_is_valid(digits, suffix)
         }
     }
 
     fn number_is_float(value: &str) -> bool {
-        if value.contains('.') {
-            true
-        } else if value.starts_with("0x") || value.ends_with("size") {
-            false
-        } else {
-            value.contains('e') || value.contains('E')
+        if value.starts_with("0x") || value.starts_with("0o") || value.starts_with("0b") {
+            return false;
+        }
+        let (_, rest) = split_int_suffix(value);
+        match byte(rest, 0) {
+            b'.' | b'e' | b'E' => true,
+            b'f' => rest.len() > 1 && rest[1..].bytes().all(|b| b'0' <= b && b <= b'9'),
+            _ => false,
+        }
+    }
+
+    fn int_suffix_is_valid(digits: &str, suffix: &str) -> bool {
+        let mut chars = suffix.chars();
+        match chars.next() {
+            None => true,
+            // A hexadecimal float like `0x1p4`. These are not valid Rust but
+            // may be produced by other tools; they must not be mistaken for
+            // the integer `0x1` with a suffix of `p4`.
+            Some('p') | Some('P') if digits.starts_with("0x") => false,
+            Some(ch) => {
+                UnicodeXID::is_xid_start(ch) && chars.all(UnicodeXID::is_xid_continue)
+            }
+        }
+    }
+
+    /// Split an integer token into the digits, including any radix prefix and
+    /// underscores, and everything after them.
+    pub fn split_int_suffix(s: &str) -> (&str, &str) {
+        let (prefix, base) = match (byte(s, 0), byte(s, 1)) {
+            (b'0', b'x') => (2, 16),
+            (b'0', b'o') => (2, 8),
+            (b'0', b'b') => (2, 2),
+            _ => (0, 10),
+        };
+        let end = s[prefix..]
+            .find(|ch: char| ch != '_' && !ch.is_digit(base))
+            .map_or(s.len(), |i| prefix + i);
+        (&s[..end], &s[end..])
+    }
+
+    /// Split an integer or float token into the number, including any radix
+    /// prefix, fraction, exponent and underscores, and the suffix after it.
+    ///
+    /// The suffix is everything that follows the number, whether or not it
+    /// names a type, so `1024MiB` splits into `1024` and `MiB`.
+    pub fn split_suffix(s: &str) -> (&str, &str) {
+        match (byte(s, 0), byte(s, 1)) {
+            (b'0', b'x') | (b'0', b'o') | (b'0', b'b') => split_int_suffix(s),
+            _ => s.split_at(float_number_len(s)),
         }
     }
 
@@ -580,81 +3285,217 @@ mod value {
         }
     }
 
+    pub fn is_digit(b: u8) -> bool {
+        b'0' <= b && b <= b'9'
+    }
+
+    pub fn int_suffix_str(suffix: &IntSuffix) -> &'static str {
+        match *suffix {
+            IntSuffix::I8 => "i8",
+            IntSuffix::I16 => "i16",
+            IntSuffix::I32 => "i32",
+            IntSuffix::I64 => "i64",
+            IntSuffix::I128 => "i128",
+            IntSuffix::Isize => "isize",
+            IntSuffix::U8 => "u8",
+            IntSuffix::U16 => "u16",
+            IntSuffix::U32 => "u32",
+            IntSuffix::U64 => "u64",
+            IntSuffix::U128 => "u128",
+            IntSuffix::Usize => "usize",
+            IntSuffix::None => "",
+        }
+    }
+
+    pub fn float_suffix_from_str(s: &str) -> FloatSuffix {
+        match s {
+            "f32" => FloatSuffix::F32,
+            "f64" => FloatSuffix::F64,
+            _ => FloatSuffix::None,
+        }
+    }
+
+    pub fn float_suffix_str(suffix: &FloatSuffix) -> &'static str {
+        match *suffix {
+            FloatSuffix::F32 => "f32",
+            FloatSuffix::F64 => "f64",
+            FloatSuffix::None => "",
+        }
+    }
+
+    /// The suffix named by `s`, or `IntSuffix::None` if `s` is not the name of
+    /// an integer type.
+    pub fn int_suffix_from_str(s: &str) -> IntSuffix {
+        match s {
+            "i8" => IntSuffix::I8,
+            "i16" => IntSuffix::I16,
+            "i32" => IntSuffix::I32,
+            "i64" => IntSuffix::I64,
+            "i128" => IntSuffix::I128,
+            "isize" => IntSuffix::Isize,
+            "u8" => IntSuffix::U8,
+            "u16" => IntSuffix::U16,
+            "u32" => IntSuffix::U32,
+            "u64" => IntSuffix::U64,
+            "u128" => IntSuffix::U128,
+            "usize" => IntSuffix::Usize,
+            _ => IntSuffix::None,
+        }
+    }
+
+    /// The largest value that fits in an integer with the given suffix,
+    /// capped at the 64 bits a `LitInt` can hold. `isize` and `usize` are
+    /// taken to be 64 bits.
+    pub fn int_suffix_max(suffix: &IntSuffix) -> u64 {
+        match *suffix {
+            IntSuffix::I8 => i8::max_value() as u64,
+            IntSuffix::I16 => i16::max_value() as u64,
+            IntSuffix::I32 => i32::max_value() as u64,
+            IntSuffix::I64 | IntSuffix::Isize => i64::max_value() as u64,
+            IntSuffix::U8 => u64::from(u8::max_value()),
+            IntSuffix::U16 => u64::from(u16::max_value()),
+            IntSuffix::U32 => u64::from(u32::max_value()),
+            IntSuffix::I128
+            | IntSuffix::U64
+            | IntSuffix::U128
+            | IntSuffix::Usize
+            | IntSuffix::None => u64::max_value(),
+        }
+    }
+
+    /// Whether `ch` has a glyph of its own, so that it can be shown without
+    /// an escape. See `LitChar::best`.
+    pub fn is_visible(ch: char) -> bool {
+        match ch {
+            // Invisible formatting characters.
+            '\u{ad}'
+            | '\u{61c}'
+            | '\u{180e}'
+            | '\u{200b}'...'\u{200f}'
+            | '\u{202a}'...'\u{202e}'
+            | '\u{2060}'...'\u{206f}'
+            | '\u{feff}'
+            | '\u{fff9}'...'\u{fffb}'
+            // Enclosing and other combining marks that cannot continue an
+            // identifier, and so are not caught by the check below.
+            | '\u{488}'...'\u{489}'
+            | '\u{1ab0}'...'\u{1aff}'
+            | '\u{20d0}'...'\u{20ff}'
+            | '\u{a670}'...'\u{a672}'
+            // Private use areas.
+            | '\u{e000}'...'\u{f8ff}'
+            | '\u{f0000}'...'\u{ffffd}'
+            | '\u{100000}'...'\u{10fffd}'
+            // Noncharacters.
+            | '\u{fdd0}'...'\u{fdef}' => false,
+            _ if ch as u32 & 0xfffe == 0xfffe => false,
+            _ if ch.is_control() || ch.is_whitespace() => false,
+            // A character that may continue an identifier but not start one
+            // is a combining mark, unless it is a digit, connector punctuation
+            // like `_`, or one of the middle dots.
+            _ if UnicodeXID::is_xid_continue(ch) && !UnicodeXID::is_xid_start(ch) => {
+                ch.is_numeric() || match ch {
+                    '_' | '\u{b7}' | '\u{387}' | '\u{203f}' | '\u{2040}' | '\u{2054}'
+                    | '\u{fe33}' | '\u{fe34}' | '\u{fe4d}'...'\u{fe4f}' | '\u{ff3f}' => true,
+                    _ => false,
+                }
+            }
+            _ => true,
+        }
+    }
+
     fn next_chr(s: &str) -> char {
         s.chars().next().unwrap_or('\0')
     }
 
-    pub fn parse_lit_str(s: &str) -> String {
-        match byte(s, 0) {
-            b'"' => parse_lit_str_cooked(s),
-            b'r' => parse_lit_str_raw(s),
-            _ => unreachable!(),
+    fn unexpected_escape(b: u8, kind: &str) -> String {
+        let mut message = format!("unexpected byte {:?} after \\ character in {} literal", b, kind);
+        if b == b'e' {
+            message.push_str("; there is no \\e escape, use \\x1b for the escape character");
         }
+        message
     }
 
-    // Clippy false positive
-    // https://github.com/rust-lang-nursery/rust-clippy/issues/2329
-    #[cfg_attr(feature = "cargo-clippy", allow(needless_continue))]
-    fn parse_lit_str_cooked(mut s: &str) -> String {
-        assert_eq!(byte(s, 0), b'"');
-        s = &s[1..];
+    fn unexpected_escape_char(ch: char, kind: &str) -> String {
+        if (ch as u32) < 0x80 {
+            unexpected_escape(ch as u8, kind)
+        } else {
+            format!("unexpected character {:?} after \\ character in {} literal", ch, kind)
+        }
+    }
 
-        let mut out = String::new();
-        'outer: loop {
-            let ch = match byte(s, 0) {
-                b'"' => break,
+    /// Split the character after a leading backslash off `s`, or `None` if
+    /// the backslash is the last character.
+    fn escape_char(s: &str) -> Option<(char, &str)> {
+        let mut chars = s[1..].chars();
+        chars.next().map(|ch| (ch, chars.as_str()))
+    }
+
+    /// Decode one char of the content of a cooked string, advancing `s` past
+    /// it. Returns `None` once `s` is at the closing quote.
+    pub fn next_cooked_char(s: &mut &str) -> Result<Option<char>, String> {
+        loop {
+            let ch = match byte(*s, 0) {
+                b'"' => return Ok(None),
                 b'\\' => {
-                    let b = byte(s, 1);
-                    s = &s[2..];
-                    match b {
-                        b'x' => {
-                            let (byte, rest) = backslash_x(s);
-                            s = rest;
-                            assert!(byte <= 0x80, "Invalid \\x byte in string literal");
+                    let (esc, rest) = match escape_char(*s) {
+                        Some(split) => split,
+                        None => return Err("unterminated string literal".to_owned()),
+                    };
+                    *s = rest;
+                    match esc {
+                        'x' => {
+                            let (byte, rest) = backslash_x(*s)?;
+                            *s = rest;
+                            if byte > 0x7f {
+                                return Err("Invalid \\x byte in string literal".to_owned());
+                            }
                             char::from_u32(u32::from(byte)).unwrap()
                         }
-                        b'u' => {
-                            let (chr, rest) = backslash_u(s);
-                            s = rest;
+                        'u' => {
+                            let (chr, rest) = backslash_u(*s)?;
+                            *s = rest;
                             chr
                         }
-                        b'n' => '\n',
-                        b'r' => '\r',
-                        b't' => '\t',
-                        b'\\' => '\\',
-                        b'0' => '\0',
-                        b'\'' => '\'',
-                        b'"' => '"',
-                        b'\r' | b'\n' => loop {
-                            let ch = next_chr(s);
-                            if ch.is_whitespace() {
-                                s = &s[ch.len_utf8()..];
-                            } else {
-                                continue 'outer;
-                            }
-                        },
-                        b => panic!("unexpected byte {:?} after \\ character in byte literal", b),
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        '\\' => '\\',
+                        '0' => '\0',
+                        '\'' => '\'',
+                        '"' => '"',
+                        '\r' | '\n' => {
+                            let rest = s.trim_left();
+                            *s = rest;
+                            continue;
+                        }
+                        esc => return Err(unexpected_escape_char(esc, "string")),
                     }
                 }
                 b'\r' => {
-                    assert_eq!(byte(s, 1), b'\n', "Bare CR not allowed in string");
-                    s = &s[2..];
+                    if byte(*s, 1) != b'\n' {
+                        return Err("Bare CR not allowed in string".to_owned());
+                    }
+                    *s = &s[2..];
                     '\n'
                 }
+                _ if s.is_empty() => return Err("unterminated string literal".to_owned()),
                 _ => {
-                    let ch = next_chr(s);
-                    s = &s[ch.len_utf8()..];
+                    let ch = next_chr(*s);
+                    *s = &s[ch.len_utf8()..];
                     ch
                 }
             };
-            out.push(ch);
+            return Ok(Some(ch));
         }
+    }
 
-        assert_eq!(s, "\"");
-        out
+    fn parse_lit_str_raw(s: &str) -> Result<String, String> {
+        raw_body(s).map(str::to_owned)
     }
 
-    fn parse_lit_str_raw(mut s: &str) -> String {
+    /// The content of a raw string literal between its delimiters.
+    pub fn raw_body(mut s: &str) -> Result<&str, String> {
         assert_eq!(byte(s, 0), b'r');
         s = &s[1..];
 
@@ -662,28 +3503,72 @@ mod value {
         while byte(s, pounds) == b'#' {
             pounds += 1;
         }
-        assert_eq!(byte(s, pounds), b'"');
-        assert_eq!(byte(s, s.len() - pounds - 1), b'"');
-        for end in s[s.len() - pounds..].bytes() {
-            assert_eq!(end, b'#');
+        if byte(s, pounds) != b'"' {
+            return Err("expected `\"` after `r` in raw string literal".to_owned());
+        }
+
+        // Only the leading pounds were counted, so make sure the token ends
+        // with a quote followed by exactly as many of them.
+        let rest = &s[pounds + 1..];
+        match closing_pounds(rest) {
+            Some(found) if found == pounds => Ok(&rest[..rest.len() - pounds - 1]),
+            Some(found) => Err(format!(
+                "unbalanced raw string pounds: expected {} `#`, found {}",
+                pounds, found
+            )),
+            None => Err(format!(
+                "unterminated raw string literal: expected `\"` followed by {} `#`",
+                pounds
+            )),
+        }
+    }
+
+    /// The number of `#` after the closing quote of a raw string, given the
+    /// text after its opening quote, or `None` if it has no closing quote.
+    fn closing_pounds(rest: &str) -> Option<usize> {
+        let quote = match rest.rfind('"') {
+            Some(quote) => quote,
+            None => return None,
+        };
+        if rest[quote + 1..].bytes().all(|b| b == b'#') {
+            Some(rest.len() - quote - 1)
+        } else {
+            None
         }
+    }
 
-        s[pounds + 1..s.len() - pounds - 1].to_owned()
+    /// The number of `#` expected and found in the closing delimiter of a raw
+    /// string or raw byte string token, if they differ.
+    pub fn raw_pound_mismatch(s: &str) -> Option<(usize, usize)> {
+        let s = if byte(s, 0) == b'b' { &s[1..] } else { s };
+        if byte(s, 0) != b'r' {
+            return None;
+        }
+        let s = &s[1..];
+        let mut pounds = 0;
+        while byte(s, pounds) == b'#' {
+            pounds += 1;
+        }
+        if byte(s, pounds) != b'"' {
+            return None;
+        }
+        match closing_pounds(&s[pounds + 1..]) {
+            Some(found) if found == pounds => None,
+            Some(found) => Some((pounds, found)),
+            None => Some((pounds, 0)),
+        }
     }
 
-    pub fn parse_lit_byte_str(s: &str) -> Vec<u8> {
+    pub fn parse_lit_byte_str(s: &str) -> Result<Vec<u8>, String> {
         assert_eq!(byte(s, 0), b'b');
         match byte(s, 1) {
             b'"' => parse_lit_byte_str_cooked(s),
             b'r' => parse_lit_byte_str_raw(s),
-            _ => unreachable!(),
+            _ => Err("expected byte string literal".to_owned()),
         }
     }
 
-    // Clippy false positive
-    // https://github.com/rust-lang-nursery/rust-clippy/issues/2329
-    #[cfg_attr(feature = "cargo-clippy", allow(needless_continue))]
-    fn parse_lit_byte_str_cooked(mut s: &str) -> Vec<u8> {
+    fn parse_lit_byte_str_cooked(mut s: &str) -> Result<Vec<u8>, String> {
         assert_eq!(byte(s, 0), b'b');
         assert_eq!(byte(s, 1), b'"');
         s = &s[2..];
@@ -692,16 +3577,32 @@ mod value {
         let mut s = s.as_bytes();
 
         let mut out = Vec::new();
-        'outer: loop {
-            let byte = match byte(s, 0) {
-                b'"' => break,
+        while let Some(byte) = next_cooked_byte(&mut s)? {
+            out.push(byte);
+        }
+
+        if s != b"\"" {
+            return Err("unexpected characters after end of byte string literal".to_owned());
+        }
+        Ok(out)
+    }
+
+    /// Decode one byte of the content of a cooked byte string, advancing `s`
+    /// past it. Returns `None` once `s` is at the closing quote.
+    pub fn next_cooked_byte(s: &mut &[u8]) -> Result<Option<u8>, String> {
+        loop {
+            let b = match byte(*s, 0) {
+                b'"' => return Ok(None),
+                b'\\' if s.len() < 2 => {
+                    return Err("unterminated byte string literal".to_owned())
+                }
                 b'\\' => {
-                    let b = byte(s, 1);
-                    s = &s[2..];
+                    let b = byte(*s, 1);
+                    *s = &s[2..];
                     match b {
                         b'x' => {
-                            let (b, rest) = backslash_x(s);
-                            s = rest;
+                            let (b, rest) = backslash_x(*s)?;
+                            *s = rest;
                             b
                         }
                         b'n' => b'\n',
@@ -711,41 +3612,58 @@ mod value {
                         b'0' => b'\0',
                         b'\'' => b'\'',
                         b'"' => b'"',
-                        b'\r' | b'\n' => loop {
-                            let byte = byte(s, 0);
-                            let ch = char::from_u32(u32::from(byte)).unwrap();
-                            if ch.is_whitespace() {
-                                s = &s[1..];
-                            } else {
-                                continue 'outer;
+                        b'\r' | b'\n' => {
+                            while char::from_u32(u32::from(byte(*s, 0))).unwrap().is_whitespace() {
+                                *s = &s[1..];
                             }
-                        },
-                        b => panic!("unexpected byte {:?} after \\ character in byte literal", b),
+                            continue;
+                        }
+                        b => return Err(unexpected_escape(b, "byte")),
                     }
                 }
                 b'\r' => {
-                    assert_eq!(byte(s, 1), b'\n', "Bare CR not allowed in string");
-                    s = &s[2..];
+                    if byte(*s, 1) != b'\n' {
+                        return Err("Bare CR not allowed in string".to_owned());
+                    }
+                    *s = &s[2..];
                     b'\n'
                 }
+                _ if s.is_empty() => return Err("unterminated byte string literal".to_owned()),
                 b => {
-                    s = &s[1..];
+                    *s = &s[1..];
                     b
                 }
             };
-            out.push(byte);
+            return Ok(Some(b));
         }
+    }
 
-        assert_eq!(s, b"\"");
-        out
+    /// Check that a byte string literal decodes successfully, without
+    /// building the decoded value.
+    pub fn validate_lit_byte_str(s: &str) -> Result<(), String> {
+        assert_eq!(byte(s, 0), b'b');
+        match byte(s, 1) {
+            b'"' => {
+                let mut rest = &s.as_bytes()[2..];
+                while next_cooked_byte(&mut rest)?.is_some() {}
+                if rest != b"\"" {
+                    return Err(
+                        "unexpected characters after end of byte string literal".to_owned(),
+                    );
+                }
+                Ok(())
+            }
+            b'r' => raw_body(&s[1..]).map(|_| ()),
+            _ => Err("expected byte string literal".to_owned()),
+        }
     }
 
-    fn parse_lit_byte_str_raw(s: &str) -> Vec<u8> {
+    fn parse_lit_byte_str_raw(s: &str) -> Result<Vec<u8>, String> {
         assert_eq!(byte(s, 0), b'b');
-        parse_lit_str_raw(&s[1..]).into_bytes()
+        parse_lit_str_raw(&s[1..]).map(String::into_bytes)
     }
 
-    pub fn parse_lit_byte(s: &str) -> u8 {
+    pub fn parse_lit_byte(s: &str) -> Result<u8, String> {
         assert_eq!(byte(s, 0), b'b');
         assert_eq!(byte(s, 1), b'\'');
 
@@ -753,12 +3671,13 @@ mod value {
         let mut s = s[2..].as_bytes();
 
         let b = match byte(s, 0) {
+            b'\\' if s.len() < 2 => return Err("unterminated byte literal".to_owned()),
             b'\\' => {
                 let b = byte(s, 1);
                 s = &s[2..];
                 match b {
                     b'x' => {
-                        let (b, rest) = backslash_x(s);
+                        let (b, rest) = backslash_x(s)?;
                         s = rest;
                         b
                     }
@@ -769,60 +3688,72 @@ mod value {
                     b'0' => b'\0',
                     b'\'' => b'\'',
                     b'"' => b'"',
-                    b => panic!("unexpected byte {:?} after \\ character in byte literal", b),
+                    b => return Err(unexpected_escape(b, "byte")),
                 }
             }
+            _ if s.is_empty() => return Err("unterminated byte literal".to_owned()),
             b => {
                 s = &s[1..];
                 b
             }
         };
 
-        assert_eq!(byte(s, 0), b'\'');
-        b
+        if s != b"\'" {
+            return Err("Expected end of byte literal".to_owned());
+        }
+        Ok(b)
     }
 
-    pub fn parse_lit_char(mut s: &str) -> char {
+    pub fn parse_lit_char(mut s: &str) -> Result<char, String> {
         assert_eq!(byte(s, 0), b'\'');
         s = &s[1..];
 
         let ch = match byte(s, 0) {
+            b'\'' if s.len() == 1 => return Err("empty character literal".to_owned()),
             b'\\' => {
-                let b = byte(s, 1);
-                s = &s[2..];
-                match b {
-                    b'x' => {
-                        let (byte, rest) = backslash_x(s);
+                let (esc, rest) = match escape_char(s) {
+                    Some(split) => split,
+                    None => return Err("unterminated character literal".to_owned()),
+                };
+                s = rest;
+                match esc {
+                    'x' => {
+                        let (byte, rest) = backslash_x(s)?;
                         s = rest;
-                        assert!(byte <= 0x80, "Invalid \\x byte in string literal");
+                        if byte > 0x7f {
+                            return Err("Invalid \\x byte in character literal".to_owned());
+                        }
                         char::from_u32(u32::from(byte)).unwrap()
                     }
-                    b'u' => {
-                        let (chr, rest) = backslash_u(s);
+                    'u' => {
+                        let (chr, rest) = backslash_u(s)?;
                         s = rest;
                         chr
                     }
-                    b'n' => '\n',
-                    b'r' => '\r',
-                    b't' => '\t',
-                    b'\\' => '\\',
-                    b'0' => '\0',
-                    b'\'' => '\'',
-                    b'"' => '"',
-                    b => panic!("unexpected byte {:?} after \\ character in byte literal", b),
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '\\' => '\\',
+                    '0' => '\0',
+                    '\'' => '\'',
+                    '"' => '"',
+                    esc => return Err(unexpected_escape_char(esc, "character")),
                 }
             }
+            _ if s.is_empty() => return Err("unterminated character literal".to_owned()),
             _ => {
                 let ch = next_chr(s);
                 s = &s[ch.len_utf8()..];
                 ch
             }
         };
-        assert_eq!(s, "\'", "Expected end of char literal");
-        ch
+        if s != "\'" {
+            return Err("Expected end of char literal".to_owned());
+        }
+        Ok(ch)
     }
 
-    fn backslash_x<S>(s: &S) -> (u8, &S)
+    fn backslash_x<S>(s: &S) -> Result<(u8, &S), String>
     where
         S: Index<RangeFrom<usize>, Output = S> + AsRef<[u8]> + ?Sized,
     {
@@ -833,20 +3764,20 @@ mod value {
             b'0'...b'9' => b0 - b'0',
             b'a'...b'f' => 10 + (b0 - b'a'),
             b'A'...b'F' => 10 + (b0 - b'A'),
-            _ => panic!("unexpected non-hex character after \\x"),
+            _ => return Err("expected two hex digits after \\x".to_owned()),
         };
         ch += match b1 {
             b'0'...b'9' => b1 - b'0',
             b'a'...b'f' => 10 + (b1 - b'a'),
             b'A'...b'F' => 10 + (b1 - b'A'),
-            _ => panic!("unexpected non-hex character after \\x"),
+            _ => return Err("expected two hex digits after \\x".to_owned()),
         };
-        (ch, &s[2..])
+        Ok((ch, &s[2..]))
     }
 
-    fn backslash_u(mut s: &str) -> (char, &str) {
+    fn backslash_u(mut s: &str) -> Result<(char, &str), String> {
         if byte(s, 0) != b'{' {
-            panic!("expected {{ after \\u");
+            return Err("expected { after \\u".to_owned());
         }
         s = &s[1..];
 
@@ -870,16 +3801,20 @@ mod value {
                     s = &s[1..];
                 }
                 b'}' => break,
-                _ => panic!("unexpected non-hex character after \\u"),
+                _ => return Err("unexpected non-hex character after \\u".to_owned()),
             }
         }
-        assert!(byte(s, 0) == b'}');
+        if byte(s, 0) != b'}' {
+            return Err("expected } to close \\u escape".to_owned());
+        }
         s = &s[1..];
 
-        if let Some(ch) = char::from_u32(ch) {
-            (ch, s)
-        } else {
-            panic!("character code {:x} is not a valid unicode character", ch);
+        match char::from_u32(ch) {
+            Some(ch) => Ok((ch, s)),
+            None => Err(format!(
+                "character code {:x} is not a valid unicode character",
+                ch
+            )),
         }
     }
 
@@ -916,6 +3851,8 @@ mod value {
                 // consider these integers.
                 b'.' if base == 10 => return None,
                 b'e' | b'E' if base == 10 => return None,
+                // The suffix, if any, begins at the first byte that is not a
+                // digit, so it never contributes to the value.
                 _ => break,
             };
 
@@ -937,11 +3874,247 @@ mod value {
         Some(value)
     }
 
-    pub fn parse_lit_float(input: &str) -> f64 {
+    /// The value of digits in the given radix, with underscores skipped, or
+    /// `None` if they are malformed or too large. See `LitInt::parse_radix`.
+    #[cfg(feature = "i128")]
+    pub fn parse_int_radix(s: &str, radix: u32) -> Option<u128> {
+        assert!(
+            radix >= 2 && radix <= 36,
+            "LitInt::parse_radix: radix must be in the range 2 to 36, got {}",
+            radix
+        );
+
+        let mut value: u128 = 0;
+        let mut any_digits = false;
+        for c in s.chars() {
+            if c == '_' {
+                continue;
+            }
+            let digit = match c.to_digit(radix) {
+                Some(digit) => digit,
+                None => return None,
+            };
+            value = match value.checked_mul(u128::from(radix)) {
+                Some(value) => value,
+                None => return None,
+            };
+            value = match value.checked_add(u128::from(digit)) {
+                Some(value) => value,
+                None => return None,
+            };
+            any_digits = true;
+        }
+
+        if any_digits {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn has_uppercase_hex_escapes(s: &str) -> bool {
+        let mut bytes = s.bytes();
+        while let Some(b) = bytes.next() {
+            if b != b'\\' {
+                continue;
+            }
+            let digits = match bytes.next() {
+                Some(b'x') => bytes.clone().take(2).collect::<Vec<_>>(),
+                Some(b'u') => bytes.clone().skip(1).take_while(|&b| b != b'}').collect(),
+                _ => continue,
+            };
+            if digits.iter().any(|b| b'A' <= *b && *b <= b'F') {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn escape_count(s: &str) -> usize {
+        let mut bytes = s.bytes();
+        let mut count = 0;
+        while let Some(b) = bytes.next() {
+            if b == b'\\' {
+                // Skip the escaped byte so that `\\` counts once.
+                bytes.next();
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn int_decimal(s: &str) -> Option<String> {
+        if !number_is_int_of_any_size(s) {
+            return None;
+        }
+        let (digits, _) = split_int_suffix(s);
+        let (digits, radix) = match (byte(digits, 0), byte(digits, 1)) {
+            (b'0', b'x') => (&digits[2..], 16),
+            (b'0', b'o') => (&digits[2..], 8),
+            (b'0', b'b') => (&digits[2..], 2),
+            (b'0'...b'9', _) => (digits, 10),
+            _ => return None,
+        };
+
+        // Little-endian decimal digits of the value so far.
+        let mut decimal = vec![0u32];
+        let mut any_digits = false;
+        for c in digits.chars() {
+            if c == '_' {
+                continue;
+            }
+            let mut carry = match c.to_digit(radix) {
+                Some(digit) => digit,
+                None => return None,
+            };
+            for d in &mut decimal {
+                let x = *d * radix + carry;
+                *d = x % 10;
+                carry = x / 10;
+            }
+            while carry > 0 {
+                decimal.push(carry % 10);
+                carry /= 10;
+            }
+            any_digits = true;
+        }
+
+        if !any_digits {
+            return None;
+        }
+        while decimal.len() > 1 && decimal[decimal.len() - 1] == 0 {
+            decimal.pop();
+        }
+        Some(decimal.iter().rev().map(|&d| char::from_digit(d, 10).unwrap()).collect())
+    }
+
+    /// The value of an integer token of any size as the 16 big-endian bytes
+    /// of a `u128`, or `None` if it is not an integer or needs more than 128
+    /// bits.
+    pub fn int_be_bytes(s: &str) -> Option<Vec<u8>> {
+        let decimal = match int_decimal(s) {
+            Some(decimal) => decimal,
+            None => return None,
+        };
+        let mut bytes = vec![0u8; 16];
+        for digit in decimal.bytes() {
+            let mut carry = u32::from(digit - b'0');
+            for b in bytes.iter_mut().rev() {
+                let x = u32::from(*b) * 10 + carry;
+                *b = x as u8;
+                carry = x >> 8;
+            }
+            if carry != 0 {
+                return None;
+            }
+        }
+        Some(bytes)
+    }
+
+    pub fn format_int_radix(mut value: u64, radix: u32) -> String {
+        assert!(
+            radix >= 2 && radix <= 36,
+            "format_int_radix: radix must be in the range 2 to 36, got {}",
+            radix
+        );
+
+        let mut digits = Vec::new();
+        loop {
+            let digit = (value % u64::from(radix)) as u32;
+            digits.push(char::from_digit(digit, radix).unwrap());
+            value /= u64::from(radix);
+            if value == 0 {
+                break;
+            }
+        }
+        digits.iter().rev().cloned().collect()
+    }
+
+    /// The value of an integer token of any size, like `0xff_u8` or a
+    /// `u128` that does not fit in a `LitInt`.
+    #[cfg(feature = "i128")]
+    pub fn parse_int_u128(s: &str) -> Option<u128> {
+        if !number_is_int_of_any_size(s) {
+            return None;
+        }
+        let (digits, _) = split_int_suffix(s);
+        match (byte(digits, 0), byte(digits, 1)) {
+            (b'0', b'x') => parse_int_radix(&digits[2..], 16),
+            (b'0', b'o') => parse_int_radix(&digits[2..], 8),
+            (b'0', b'b') => parse_int_radix(&digits[2..], 2),
+            (b'0'...b'9', _) => parse_int_radix(digits, 10),
+            _ => None,
+        }
+    }
+
+    pub fn parse_lit_float(input: &str) -> Result<f64, String> {
+        let (_, suffix) = split_suffix(input);
+        if is_empty_exponent(suffix) {
+            return Err(format!(
+                "expected at least one digit in exponent of float literal `{}`",
+                input
+            ));
+        }
+        float_digits(input)
+            .parse()
+            .map_err(|_| format!("invalid float literal `{}`", input))
+    }
+
+    /// Whether the text after the number of a float literal is an `e` that
+    /// was meant to begin an exponent but has no digits, as in `1e+` or
+    /// `2.0ef64`, rather than a unit like the `em` of `1.5em`.
+    fn is_empty_exponent(suffix: &str) -> bool {
+        match byte(suffix, 0) {
+            b'e' | b'E' => {}
+            _ => return false,
+        }
+        let rest = &suffix[1..];
+        match byte(rest, 0) {
+            0 | b'+' | b'-' | b'_' => true,
+            b'f' => rest.len() > 1 && rest[1..].bytes().all(is_digit),
+            _ => false,
+        }
+    }
+
+    /// The length of the number at the start of a float literal, up to where
+    /// its suffix begins. A token built from a negative value, like `-0.0`,
+    /// keeps its sign as part of the number.
+    pub fn float_number_len(s: &str) -> usize {
+        let bytes = s.as_bytes();
+        let digits = |mut i: usize| {
+            while i < bytes.len() && (is_digit(bytes[i]) || bytes[i] == b'_') {
+                i += 1;
+            }
+            i
+        };
+
+        let sign = if byte(s, 0) == b'-' { 1 } else { 0 };
+        let mut end = digits(sign);
+        if byte(s, end) == b'.' {
+            end = digits(end + 1);
+        }
+        if byte(s, end) == b'e' || byte(s, end) == b'E' {
+            let mut exponent = end + 1;
+            if byte(s, exponent) == b'+' || byte(s, exponent) == b'-' {
+                exponent += 1;
+            }
+            while byte(s, exponent) == b'_' {
+                exponent += 1;
+            }
+            if is_digit(byte(s, exponent)) {
+                end = digits(exponent);
+            }
+        }
+        end
+    }
+
+    pub fn float_digits(input: &str) -> String {
         // Rust's floating point literals are very similar to the ones parsed by
         // the standard library, except that rust's literals can contain
-        // ignorable underscores. Let's remove those underscores.
-        let mut bytes = input.to_owned().into_bytes();
+        // ignorable underscores. Let's remove those underscores, along with
+        // the suffix.
+        let (number, _) = split_suffix(input);
+        let mut bytes = number.to_owned().into_bytes();
         let mut write = 0;
         for read in 0..bytes.len() {
             if bytes[read] == b'_' {
@@ -954,9 +4127,7 @@ mod value {
             write += 1;
         }
         bytes.truncate(write);
-        let input = String::from_utf8(bytes).unwrap();
-        let end = input.find('f').unwrap_or_else(|| input.len());
-        input[..end].parse().unwrap()
+        String::from_utf8(bytes).unwrap()
     }
 
     pub fn to_literal(s: &str) -> Literal {
@@ -966,4 +4137,18 @@ mod value {
             _ => unreachable!(),
         }
     }
+
+    /// Like `to_literal`, but returns `None` if `s` is not a single literal
+    /// token, for example because its suffix does not lex.
+    pub fn try_to_literal(s: &str) -> Option<Literal> {
+        let stream = match s.parse::<TokenStream>() {
+            Ok(stream) => stream,
+            Err(_) => return None,
+        };
+        let mut tokens = stream.into_iter();
+        match (tokens.next(), tokens.next()) {
+            (Some(TokenTree { kind: TokenNode::Literal(l), .. }), None) => Some(l),
+            _ => None,
+        }
+    }
 }
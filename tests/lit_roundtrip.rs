@@ -0,0 +1,492 @@
+// Copyright 2018 Syn Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Property tests for literals. Each property is checked against a few
+//! thousand randomly generated values, and every generated value is spelled
+//! out as a token in one of the many ways Rust allows: with or without escapes,
+//! raw or cooked, in any radix, with underscores and suffixes.
+//!
+//! The generator is seeded so that a failure reproduces on every run.
+
+#![cfg(feature = "extra-traits")]
+
+extern crate proc_macro2;
+extern crate syn;
+
+use proc_macro2::{Span, TokenNode, TokenStream};
+use syn::{FloatSuffix, IntSuffix, Lit, LitByte, LitByteStr, LitChar, LitFloat, LitInt, LitStr};
+use std::str::FromStr;
+
+const CASES: usize = 2000;
+
+/// A xorshift generator, which is plenty for picking test inputs.
+struct Rng(u64);
+
+impl Rng {
+    fn new() -> Self {
+        Rng(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    fn char(&mut self) -> char {
+        match self.below(4) {
+            0 => *self.pick(&['"', '\'', '\\', '\0', '\n', '\r', '\t', '#', '\u{7f}', ' ']),
+            1 => *self.pick(&['é', 'ß', '\u{200b}', '\u{fffd}', '😀', '\u{10ffff}']),
+            2 => (0x20 + self.below(0x5f) as u8) as char,
+            _ => loop {
+                if let Some(ch) = std::char::from_u32(self.below(0x11_0000) as u32) {
+                    break ch;
+                }
+            },
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.below(12);
+        (0..len).map(|_| self.char()).collect()
+    }
+
+    fn byte(&mut self) -> u8 {
+        match self.below(3) {
+            0 => *self.pick(&[b'"', b'\'', b'\\', b'\0', b'\n', b'\r', b'\t', b'#', 0x7f, 0xff]),
+            1 => 0x20 + self.below(0x5f) as u8,
+            _ => self.next() as u8,
+        }
+    }
+
+    fn bytes(&mut self) -> Vec<u8> {
+        let len = self.below(12);
+        (0..len).map(|_| self.byte()).collect()
+    }
+}
+
+fn lit(token: &str) -> Lit {
+    let tokens = match TokenStream::from_str(token) {
+        Ok(tokens) => tokens,
+        Err(_) => panic!("failed to lex `{}`", token),
+    };
+    match tokens.into_iter().next().unwrap().kind {
+        TokenNode::Literal(lit) => Lit::new(lit, Span::def_site()),
+        _ => panic!("not a literal: `{}`", token),
+    }
+}
+
+/// One of the spellings of `ch` inside a literal delimited by `quote`. Only
+/// characters that may appear unescaped are ever left unescaped.
+fn spell_char(rng: &mut Rng, ch: char, quote: char, out: &mut String) {
+    let must_escape = ch == quote || ch == '\\' || ch == '\r'
+        || (quote == '\'' && (ch == '\n' || ch == '\t'));
+    match rng.below(if must_escape { 3 } else { 4 }) {
+        0 => match ch {
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\\' => out.push_str("\\\\"),
+            '\0' => out.push_str("\\0"),
+            '\'' => out.push_str("\\'"),
+            '"' => out.push_str("\\\""),
+            _ => spell_char(rng, ch, quote, out),
+        },
+        1 => if (ch as u32) < 0x80 {
+            if rng.one_in(2) {
+                out.push_str(&format!("\\x{:02x}", ch as u32));
+            } else {
+                out.push_str(&format!("\\x{:02X}", ch as u32));
+            }
+        } else {
+            spell_char(rng, ch, quote, out)
+        },
+        2 => {
+            let zeros = rng.below(7 - format!("{:x}", ch as u32).len() as u64) as usize;
+            let digits = if rng.one_in(2) {
+                format!("{:x}", ch as u32)
+            } else {
+                format!("{:X}", ch as u32)
+            };
+            out.push_str(&format!("\\u{{{}{}}}", "0".repeat(zeros), digits));
+        }
+        _ => out.push(ch),
+    }
+}
+
+/// Like `spell_char` for bytes in a byte or byte string literal, where only
+/// ASCII may appear unescaped and there are no unicode escapes.
+fn spell_byte(rng: &mut Rng, b: u8, quote: u8, out: &mut String) {
+    let must_escape = b == quote || b == b'\\' || b == b'\r' || b >= 0x80
+        || (quote == b'\'' && (b == b'\n' || b == b'\t'));
+    match rng.below(if must_escape { 2 } else { 3 }) {
+        0 => out.push_str(&format!("\\x{:02x}", b)),
+        1 => match b {
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            b'\\' => out.push_str("\\\\"),
+            b'\0' => out.push_str("\\0"),
+            b'\'' => out.push_str("\\'"),
+            b'"' => out.push_str("\\\""),
+            _ => out.push_str(&format!("\\x{:02X}", b)),
+        },
+        _ => out.push(b as char),
+    }
+}
+
+/// Raw strings need enough `#` that the body cannot close them early.
+fn raw_pounds(rng: &mut Rng, body: &str) -> String {
+    let mut pounds = String::new();
+    while body.contains(&format!("\"{}", pounds)) {
+        pounds.push('#');
+    }
+    for _ in 0..rng.below(3) {
+        pounds.push('#');
+    }
+    pounds
+}
+
+fn spell_str(rng: &mut Rng, value: &str) -> String {
+    if rng.one_in(3) && !value.contains('\r') {
+        let pounds = raw_pounds(rng, value);
+        return format!("r{}\"{}\"{}", pounds, value, pounds);
+    }
+    let mut token = String::from("\"");
+    for ch in value.chars() {
+        // A line continuation also swallows any whitespace after it.
+        if !ch.is_whitespace() && rng.one_in(8) {
+            token.push_str("\\\n    ");
+        }
+        spell_char(rng, ch, '"', &mut token);
+    }
+    token.push('"');
+    token
+}
+
+fn spell_byte_str(rng: &mut Rng, value: &[u8]) -> String {
+    let plain = value.iter().all(|&b| b < 0x80 && b != b'\r');
+    if rng.one_in(3) && plain {
+        let body = String::from_utf8(value.to_vec()).unwrap();
+        let pounds = raw_pounds(rng, &body);
+        return format!("br{}\"{}\"{}", pounds, body, pounds);
+    }
+    let mut token = String::from("b\"");
+    for &b in value {
+        if !(b as char).is_whitespace() && rng.one_in(8) {
+            token.push_str("\\\n    ");
+        }
+        spell_byte(rng, b, b'"', &mut token);
+    }
+    token.push('"');
+    token
+}
+
+const INT_SUFFIXES: &[(&str, u64)] = &[
+    ("", u64::max_value()),
+    ("u8", 0xff),
+    ("i8", 0x7f),
+    ("u16", 0xffff),
+    ("i16", 0x7fff),
+    ("u32", 0xffff_ffff),
+    ("i32", 0x7fff_ffff),
+    ("u64", u64::max_value()),
+    ("i64", 0x7fff_ffff_ffff_ffff),
+    ("u128", u64::max_value()),
+    ("i128", u64::max_value()),
+    ("usize", 0xffff_ffff),
+    ("isize", 0x7fff_ffff),
+];
+
+fn spell_int(rng: &mut Rng, value: u64, suffix: &str) -> String {
+    let (prefix, digits) = match rng.below(4) {
+        0 => ("0x", if rng.one_in(2) {
+            format!("{:x}", value)
+        } else {
+            format!("{:X}", value)
+        }),
+        1 => ("0o", format!("{:o}", value)),
+        2 => ("0b", format!("{:b}", value)),
+        _ => ("", format!("{}", value)),
+    };
+    let mut token = String::from(prefix);
+    for _ in 0..rng.below(3) {
+        token.push('0');
+    }
+    for ch in digits.chars() {
+        token.push(ch);
+        if rng.one_in(4) {
+            token.push('_');
+        }
+    }
+    if !suffix.is_empty() && rng.one_in(3) {
+        token.push('_');
+    }
+    token.push_str(suffix);
+    token
+}
+
+fn random_int(rng: &mut Rng, max: u64) -> u64 {
+    let value = match rng.below(3) {
+        0 => rng.below(256),
+        1 => rng.next() >> rng.below(64),
+        _ => rng.next(),
+    };
+    if max == u64::max_value() {
+        value
+    } else {
+        value % (max + 1)
+    }
+}
+
+fn random_float(rng: &mut Rng) -> f64 {
+    loop {
+        let value = match rng.below(3) {
+            0 => rng.below(1000) as f64 / 8.0,
+            1 => rng.next() as f64 * 1e-10,
+            _ => f64::from_bits(rng.next()).abs(),
+        };
+        if value.is_finite() {
+            return value;
+        }
+    }
+}
+
+/// Spells a finite non-negative float so that it parses back to exactly the
+/// same value.
+fn spell_float(rng: &mut Rng, value: f64, suffix: &str) -> String {
+    let mut token = match rng.below(3) {
+        0 => format!("{:e}", value),
+        1 => format!("{:E}", value),
+        _ => format!("{:?}", value),
+    };
+    if !token.contains('.') && !token.contains('e') && !token.contains('E') {
+        token.push_str(".0");
+    }
+    if rng.one_in(4) && token[1..].starts_with(|ch: char| ch.is_ascii_digit()) {
+        token.insert(1, '_');
+    }
+    token.push_str(suffix);
+    token
+}
+
+#[test]
+fn str_tokens() {
+    let mut rng = Rng::new();
+    for _ in 0..CASES {
+        let value = rng.string();
+        let token = spell_str(&mut rng, &value);
+        match lit(&token) {
+            Lit::Str(lit) => {
+                assert!(lit.validate().is_ok(), "{}", token);
+                assert_eq!(lit.try_value().ok(), Some(value.clone()), "{}", token);
+            }
+            wrong => panic!("`{}` parsed as {:?}", token, wrong),
+        }
+    }
+}
+
+#[test]
+fn byte_str_tokens() {
+    let mut rng = Rng::new();
+    for _ in 0..CASES {
+        let value = rng.bytes();
+        let token = spell_byte_str(&mut rng, &value);
+        match lit(&token) {
+            Lit::ByteStr(lit) => {
+                assert!(lit.validate().is_ok(), "{}", token);
+                assert_eq!(lit.try_value().ok(), Some(value.clone()), "{}", token);
+            }
+            wrong => panic!("`{}` parsed as {:?}", token, wrong),
+        }
+    }
+}
+
+#[test]
+fn char_tokens() {
+    let mut rng = Rng::new();
+    for _ in 0..CASES {
+        let value = rng.char();
+        let mut token = String::from("'");
+        spell_char(&mut rng, value, '\'', &mut token);
+        token.push('\'');
+        match lit(&token) {
+            Lit::Char(lit) => {
+                assert!(lit.validate().is_ok(), "{}", token);
+                assert_eq!(lit.try_value().ok(), Some(value), "{}", token);
+            }
+            wrong => panic!("`{}` parsed as {:?}", token, wrong),
+        }
+    }
+}
+
+#[test]
+fn byte_tokens() {
+    let mut rng = Rng::new();
+    for _ in 0..CASES {
+        let value = rng.byte();
+        let mut token = String::from("b'");
+        spell_byte(&mut rng, value, b'\'', &mut token);
+        token.push('\'');
+        match lit(&token) {
+            Lit::Byte(lit) => {
+                assert!(lit.validate().is_ok(), "{}", token);
+                assert_eq!(lit.try_value().ok(), Some(value), "{}", token);
+            }
+            wrong => panic!("`{}` parsed as {:?}", token, wrong),
+        }
+    }
+}
+
+#[test]
+fn int_tokens() {
+    let mut rng = Rng::new();
+    for _ in 0..CASES {
+        let &(suffix, max) = rng.pick(INT_SUFFIXES);
+        let value = random_int(&mut rng, max);
+        let token = spell_int(&mut rng, value, suffix);
+        match lit(&token) {
+            Lit::Int(lit) => {
+                assert_eq!(lit.value(), value, "{}", token);
+                assert_eq!(lit.suffix_str(), suffix, "{}", token);
+            }
+            wrong => panic!("`{}` parsed as {:?}", token, wrong),
+        }
+    }
+}
+
+#[test]
+fn float_tokens() {
+    let mut rng = Rng::new();
+    for _ in 0..CASES {
+        let value = random_float(&mut rng);
+        let suffix = *rng.pick(&["", "f64", "_f64"]);
+        let token = spell_float(&mut rng, value, suffix);
+        match lit(&token) {
+            Lit::Float(lit) => {
+                assert_eq!(lit.try_value().ok(), Some(value), "{}", token);
+            }
+            wrong => panic!("`{}` parsed as {:?}", token, wrong),
+        }
+    }
+}
+
+#[test]
+fn constructors_round_trip() {
+    let mut rng = Rng::new();
+    let span = Span::def_site();
+    for _ in 0..CASES {
+        let value = rng.string();
+        let lit = LitStr::new(&value, span);
+        assert_eq!(lit.value(), value);
+        assert_eq!(reparse(&Lit::Str(lit)), Lit::Str(LitStr::new(&value, span)));
+
+        let value = rng.bytes();
+        let lit = LitByteStr::new(&value, span);
+        assert_eq!(lit.value(), value);
+        assert_eq!(reparse(&Lit::ByteStr(lit)), Lit::ByteStr(LitByteStr::new(&value, span)));
+
+        let value = rng.char();
+        let lit = LitChar::new(value, span);
+        assert_eq!(lit.value(), value);
+        assert_eq!(reparse(&Lit::Char(lit)), Lit::Char(LitChar::new(value, span)));
+
+        let value = rng.byte();
+        let lit = LitByte::new(value, span);
+        assert_eq!(lit.value(), value);
+        assert_eq!(reparse(&Lit::Byte(lit)), Lit::Byte(LitByte::new(value, span)));
+
+        let &(suffix, max) = rng.pick(INT_SUFFIXES);
+        let value = random_int(&mut rng, max);
+        let lit = LitInt::new(value, int_suffix(suffix), span);
+        assert_eq!(lit.value(), value, "{}{}", value, suffix);
+        assert_eq!(lit.suffix_str(), suffix);
+        assert_eq!(reparse(&Lit::Int(lit.clone())), Lit::Int(lit));
+
+        let value = random_float(&mut rng);
+        let lit = LitFloat::new(value, FloatSuffix::F64, span);
+        assert_eq!(lit.value(), value);
+        assert_eq!(reparse(&Lit::Float(lit.clone())), Lit::Float(lit));
+
+        let value = value as f32;
+        if value.is_finite() {
+            let lit = LitFloat::new(f64::from(value), FloatSuffix::F32, span);
+            assert_eq!(lit.value() as f32, value);
+        }
+    }
+}
+
+/// Prints the literal and parses the printed token again.
+fn reparse(original: &Lit) -> Lit {
+    lit(&TokenStream::from(original.clone()).to_string())
+}
+
+fn int_suffix(suffix: &str) -> IntSuffix {
+    match suffix {
+        "u8" => IntSuffix::U8,
+        "i8" => IntSuffix::I8,
+        "u16" => IntSuffix::U16,
+        "i16" => IntSuffix::I16,
+        "u32" => IntSuffix::U32,
+        "i32" => IntSuffix::I32,
+        "u64" => IntSuffix::U64,
+        "i64" => IntSuffix::I64,
+        "u128" => IntSuffix::U128,
+        "i128" => IntSuffix::I128,
+        "usize" => IntSuffix::Usize,
+        "isize" => IntSuffix::Isize,
+        _ => IntSuffix::None,
+    }
+}
+
+#[cfg(feature = "lit-encoding")]
+#[test]
+fn decode_arbitrary_bytes() {
+    let mut rng = Rng::new();
+    let span = Span::def_site();
+    for _ in 0..CASES * 5 {
+        // Start from a valid encoding and damage it, so that inputs get past
+        // the header checks as well as failing them.
+        let mut encoded = Vec::new();
+        match rng.below(4) {
+            0 => Lit::Str(LitStr::new(&rng.string(), span)).encode(&mut encoded),
+            1 => Lit::ByteStr(LitByteStr::new(&rng.bytes(), span)).encode(&mut encoded),
+            2 => Lit::Int(LitInt::new(rng.next(), IntSuffix::None, span)).encode(&mut encoded),
+            _ => {}
+        }
+        for _ in 0..rng.below(4) {
+            match rng.below(3) {
+                0 if !encoded.is_empty() => {
+                    let at = rng.below(encoded.len() as u64) as usize;
+                    encoded[at] = rng.byte();
+                }
+                1 if !encoded.is_empty() => {
+                    let len = rng.below(encoded.len() as u64) as usize;
+                    encoded.truncate(len);
+                }
+                _ => encoded.extend(rng.bytes()),
+            }
+        }
+
+        let mut bytes = &encoded[..];
+        while Lit::decode(&mut bytes).is_ok() {}
+    }
+}
@@ -11,8 +11,9 @@ extern crate quote;
 extern crate syn;
 
 use syn::{FloatSuffix, IntSuffix, Lit};
+use syn::synom::Parser;
 use quote::ToTokens;
-use proc_macro2::{Span, TokenNode, TokenStream};
+use proc_macro2::{Delimiter, Literal, Span, TokenNode, TokenStream, TokenTree};
 use std::str::FromStr;
 
 fn lit(s: &str) -> Lit {
@@ -28,6 +29,36 @@ fn lit(s: &str) -> Lit {
     }
 }
 
+// Builds a literal from a token that the proc-macro2 lexer would not produce,
+// such as one with a suffix unknown to this version of Rust.
+fn lit_unchecked(s: &str) -> Lit {
+    Lit::new(Literal::doccomment(s), Span::def_site())
+}
+
+// Builds a literal with `lit_unchecked` and unwraps the variant a test expects.
+macro_rules! lit_variants {
+    ($($name:ident: $variant:ident($ty:ident),)*) => {
+        $(
+            fn $name(s: &str) -> syn::$ty {
+                match lit_unchecked(s) {
+                    Lit::$variant(lit) => lit,
+                    wrong => panic!("{:?}", wrong),
+                }
+            }
+        )*
+    };
+}
+
+lit_variants! {
+    lit_str: Str(LitStr),
+    lit_byte_str: ByteStr(LitByteStr),
+    lit_byte: Byte(LitByte),
+    lit_char: Char(LitChar),
+    lit_int: Int(LitInt),
+    lit_float: Float(LitFloat),
+    lit_verbatim: Verbatim(LitVerbatim),
+}
+
 #[test]
 fn strings() {
     fn test_string(s: &str, value: &str) {
@@ -195,3 +226,2311 @@ fn floats() {
     test_float("1.0__3e-12", 1.03e-12, None);
     test_float("1.03e+12", 1.03e12, None);
 }
+
+#[test]
+fn float_value_str() {
+    fn test_value_str(s: &str, digits: &str) {
+        assert_eq!(lit_float(s).value_str(), digits);
+    }
+
+    test_value_str("3.14159265358979323846", "3.14159265358979323846");
+    test_value_str("3.141_592_653_589_793_238_46f64", "3.14159265358979323846");
+    test_value_str("1.5e10f32", "1.5e10");
+}
+
+#[test]
+fn int_leading_zero() {
+    fn test_leading_zero(s: &str, expected: bool) {
+        assert_eq!(lit_int(s).has_leading_zero(), expected, "{}", s);
+    }
+
+    test_leading_zero("0755", true);
+    test_leading_zero("0_755u32", true);
+    test_leading_zero("00", true);
+    test_leading_zero("0", false);
+    test_leading_zero("0u8", false);
+    test_leading_zero("755", false);
+    test_leading_zero("0x0755", false);
+    test_leading_zero("0o755", false);
+    test_leading_zero("0b0101", false);
+    test_leading_zero("007", true);
+    test_leading_zero("0x00ff", false);
+}
+
+#[test]
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+fn float_unknown_suffix() {
+    let lit = lit_float("1.0f16");
+    assert_eq!(lit.value(), 1.0);
+    assert_eq!(lit.suffix(), FloatSuffix::None);
+    assert_eq!(lit.suffix_str(), "f16");
+
+    let lit = lit_float("2.5e3f128");
+    assert_eq!(lit.value(), 2.5e3);
+    assert_eq!(lit.suffix(), FloatSuffix::None);
+    assert_eq!(lit.suffix_str(), "f128");
+}
+
+#[test]
+fn display_escaped() {
+    assert_eq!(lit("\"a\\tb\"").display_escaped(), "\"a\\tb\"");
+    assert_eq!(lit("r\"a\tb\"").display_escaped(), "\"a\\tb\"");
+    assert_eq!(lit("'x'").display_escaped(), "'x'");
+    assert_eq!(lit("'\\n'").display_escaped(), "'\\n'");
+    assert_eq!(lit("b'\\x7f'").display_escaped(), "b'\\x7f'");
+    assert_eq!(lit("b\"a\\x00\"").display_escaped(), "b\"a\\x00\"");
+    assert_eq!(lit("0xffu8").display_escaped(), "255u8");
+    assert_eq!(lit("1_000").display_escaped(), "1000");
+    assert_eq!(lit("1e3f32").display_escaped(), "1000.0f32");
+    assert_eq!(lit("1e400").display_escaped(), "1e400");
+}
+
+#[test]
+fn display_escaped_nests() {
+    fn nested(s: &str) -> String {
+        let escaped = lit(s).display_escaped();
+        // Splice the body into a larger string literal and decode it again.
+        let body = &escaped[escaped.find('"').unwrap() + 1..escaped.len() - 1];
+        lit_str(&format!("\"<{}>\"", body)).value()
+    }
+
+    assert_eq!(lit(r#""say \"hi\"""#).display_escaped(), r#""say \"hi\"""#);
+    assert_eq!(lit(r#"r"C:\dir""#).display_escaped(), r#""C:\\dir""#);
+    assert_eq!(lit(r#""a\nb""#).display_escaped(), r#""a\nb""#);
+    assert_eq!(lit("r\"a\nb\"").display_escaped(), r#""a\nb""#);
+    assert_eq!(lit(r#"b"\"\\""#).display_escaped(), r#"b"\"\\""#);
+    assert_eq!(lit(r#"'"'"#).display_escaped(), r#"'\"'"#);
+    assert_eq!(lit(r#"'\\'"#).display_escaped(), r#"'\\'"#);
+    assert_eq!(lit(r#"b'"'"#).display_escaped(), r#"b'\"'"#);
+
+    assert_eq!(nested(r#""say \"hi\"""#), "<say \"hi\">");
+    assert_eq!(nested(r#"r"C:\dir""#), "<C:\\dir>");
+    assert_eq!(nested("r\"a\nb\""), "<a\nb>");
+}
+
+#[test]
+fn as_bytes() {
+    assert_eq!(lit("b'A'").as_bytes(), Some(b"A".to_vec()));
+    assert_eq!(lit("b\"A\\x42C\"").as_bytes(), Some(b"ABC".to_vec()));
+    assert_eq!(lit("\"é\"").as_bytes(), Some("é".as_bytes().to_vec()));
+    assert_eq!(lit("65").as_bytes(), None);
+}
+
+#[test]
+fn int_u64_boundary() {
+    fn test_max(s: &str) {
+        assert_eq!(lit_int(s).value(), u64::max_value());
+    }
+
+    fn test_overflow(s: &str) {
+        assert_eq!(lit_verbatim(s).token.to_string(), s);
+    }
+
+    test_max("18446744073709551615");
+    test_max("18446744073709551615u64");
+    test_max("18_446_744_073_709_551_615");
+    test_max("0xffffffffffffffff");
+    test_max("0xFFFF_FFFF_FFFF_FFFFu64");
+    test_max("0o1777777777777777777777");
+    test_overflow("18446744073709551616");
+    test_overflow("18446744073709551616u128");
+    test_overflow("18_446_744_073_709_551_616");
+    test_overflow("0x10000000000000000");
+    test_overflow("0x1_0000_0000_0000_0000u128");
+}
+
+#[test]
+fn respanned() {
+    for s in &["\"a\"", "b\"a\"", "b'a'", "'a'", "1u8", "1.0", "1u128"] {
+        let original = lit(s);
+        let respanned = original.respanned(Span::call_site());
+        assert_eq!(respanned, original);
+        assert_eq!(
+            format!("{:?}", respanned.span()),
+            format!("{:?}", Span::call_site())
+        );
+    }
+
+    let original = Lit::Bool(syn::LitBool {
+        value: true,
+        span: Span::def_site(),
+    });
+    assert_eq!(original.respanned(Span::call_site()), original);
+}
+
+#[test]
+fn original_spelling() {
+    for s in &[
+        "\"a\\x41\"",
+        "r#\"raw\"#",
+        "b\"\\xFF\"",
+        "b'\\x7F'",
+        "'\\u{1F415}'",
+        "0xFF_u8",
+        "1_000",
+        "1.5E+10_f64",
+    ] {
+        let lit = lit(s);
+        assert_eq!(lit.original_spelling(), *s);
+        assert_eq!(lit.into_tokens().to_string(), *s);
+    }
+
+    assert_eq!(lit("18446744073709551616").original_spelling(), "18446744073709551616");
+    let bool_lit = Lit::Bool(syn::LitBool {
+        value: true,
+        span: Span::def_site(),
+    });
+    assert_eq!(bool_lit.original_spelling(), "true");
+}
+
+#[test]
+fn hex_float_is_verbatim() {
+    for s in &["0x1p4", "0x1P-4", "0x1.8p3", "0xAp+2f32"] {
+        assert_eq!(lit_verbatim(s).token.to_string(), *s);
+    }
+}
+
+#[test]
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+fn float_suffix_without_dot() {
+    for &(s, ref suffix) in &[("1f32", FloatSuffix::F32), ("1_000f64", FloatSuffix::F64)] {
+        let lit = lit_float(s);
+        assert_eq!(lit.suffix(), *suffix);
+        assert_eq!(lit.value().fract(), 0.0);
+    }
+}
+
+#[test]
+fn byte_string_bytes() {
+    fn test_bytes(s: &str) {
+        let lit = lit_byte_str(s);
+        assert_eq!(lit.bytes().collect::<Vec<u8>>(), lit.value());
+    }
+
+    test_bytes("b\"\\x00\\x7F\\xff\"");
+    test_bytes("b\"line\\\n    continued\\r\\n\"");
+    test_bytes("b\"\"");
+    test_bytes("br\"raw\\x00\"");
+    test_bytes("br##\"raw \"# string\"##");
+}
+
+#[test]
+fn parse_eq_value() {
+    let parsed = Lit::parse_eq_value.parse_str("= \"value\"").unwrap();
+    assert_eq!(parsed, lit("\"value\""));
+
+    let parsed = Lit::parse_eq_value.parse_str("= false").unwrap();
+    assert_eq!(parsed.display_escaped(), "false");
+
+    let err = Lit::parse_eq_value.parse_str("\"value\"").unwrap_err();
+    assert_eq!(err.to_string(), "expected `=` before literal");
+
+    let err = Lit::parse_eq_value.parse_str("= value").unwrap_err();
+    assert_eq!(err.to_string(), "expected literal after `=`");
+}
+
+#[test]
+fn int_checked_arithmetic() {
+    fn render(lit: Option<syn::LitInt>) -> Option<String> {
+        lit.map(|lit| lit.into_tokens().to_string())
+    }
+
+    assert_eq!(render(lit_int("2").checked_add(&lit_int("3"))), Some("5".to_owned()));
+    assert_eq!(render(lit_int("2u16").checked_add(&lit_int("3"))), Some("5u16".to_owned()));
+    assert_eq!(render(lit_int("2").checked_mul(&lit_int("3u16"))), Some("6u16".to_owned()));
+    assert_eq!(render(lit_int("5i32").checked_sub(&lit_int("3i32"))), Some("2i32".to_owned()));
+    assert_eq!(render(lit_int("2u16").checked_add(&lit_int("3u32"))), None);
+    assert_eq!(render(lit_int("3").checked_sub(&lit_int("5"))), None);
+    assert_eq!(render(lit_int("200u8").checked_add(&lit_int("56u8"))), None);
+    assert_eq!(render(lit_int("127i8").checked_add(&lit_int("1"))), None);
+    assert_eq!(render(lit_int("1_u8").checked_add(&lit_int("2u8"))), Some("3u8".to_owned()));
+    assert_eq!(render(lit_int("1ms").checked_add(&lit_int("1s"))), None);
+    assert_eq!(render(lit_int("1ms").checked_add(&lit_int("1ms"))), None);
+    assert_eq!(render(lit_int("1u256").checked_add(&lit_int("1"))), None);
+    assert_eq!(render(lit_int("1").checked_add(&lit_int("1u256"))), None);
+
+    let max = lit_int("18446744073709551615");
+    assert_eq!(
+        render(max.checked_add(&lit_int("0"))),
+        Some("18446744073709551615".to_owned())
+    );
+    assert_eq!(render(max.checked_add(&lit_int("1"))), None);
+    assert_eq!(render(lit_int("4294967296u64").checked_mul(&lit_int("4294967296"))), None);
+}
+
+#[test]
+fn into_token_stream() {
+    for s in &[
+        "\"a\"", "b\"a\"", "b'a'", "'a'", "1u8", "1.0", "true", "false",
+        "18446744073709551616",
+    ] {
+        let original = syn::parse_str::<Lit>(s).unwrap();
+        let stream = TokenStream::from(&original);
+        assert_eq!(stream.to_string(), *s);
+        assert_eq!(syn::parse2::<Lit>(stream).unwrap(), original);
+        assert_eq!(syn::parse2::<Lit>(original.clone().into()).unwrap(), original);
+    }
+}
+
+#[test]
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+fn float_exponent_plus() {
+    for &(s, has_plus, without, with) in &[
+        ("1e+10", true, "1e10", "1e+10"),
+        ("1e10", false, "1e10", "1e+10"),
+        ("1e-10", false, "1e-10", "1e-10"),
+        ("1e+10f64", true, "1e10f64", "1e+10f64"),
+        ("1.5E+3_f32", true, "1.5E3_f32", "1.5E+3_f32"),
+        ("2.5", false, "2.5", "2.5"),
+    ] {
+        let lit = lit_float(s);
+        assert_eq!(lit.exponent_has_plus(), has_plus, "{}", s);
+
+        let stripped = lit.normalize_exponent(false);
+        assert_eq!(stripped.clone().into_tokens().to_string(), without);
+        assert!(!stripped.exponent_has_plus());
+        assert_eq!(stripped.value(), lit.value());
+        assert_eq!(stripped.suffix(), lit.suffix());
+
+        let plussed = lit.normalize_exponent(true);
+        assert_eq!(plussed.clone().into_tokens().to_string(), with);
+        assert_eq!(plussed.value(), lit.value());
+        assert_eq!(plussed.suffix(), lit.suffix());
+    }
+}
+
+#[test]
+#[cfg(feature = "i128")]
+fn int_radix() {
+    assert_eq!(syn::LitInt::parse_radix("zz", 36), Some(1295));
+    assert_eq!(syn::LitInt::parse_radix("ZZ", 36), Some(1295));
+    assert_eq!(syn::LitInt::parse_radix("ff", 16), Some(255));
+    assert_eq!(syn::LitInt::parse_radix("1_000", 10), Some(1000));
+    assert_eq!(syn::LitInt::parse_radix("777", 8), Some(0o777));
+    assert_eq!(
+        syn::LitInt::parse_radix("340282366920938463463374607431768211455", 10),
+        Some(u128::max_value())
+    );
+
+    assert_eq!(syn::LitInt::parse_radix("340282366920938463463374607431768211456", 10), None);
+    assert_eq!(syn::LitInt::parse_radix("2", 2), None);
+    assert_eq!(syn::LitInt::parse_radix("z", 35), None);
+    assert_eq!(syn::LitInt::parse_radix("", 10), None);
+    assert_eq!(syn::LitInt::parse_radix("_", 10), None);
+    assert_eq!(syn::LitInt::parse_radix("-1", 10), None);
+}
+
+#[test]
+fn char_escaped_string() {
+    fn escaped(s: &str) -> String {
+        lit_char(s).to_escaped_string()
+    }
+
+    assert_eq!(escaped("'a'"), "a");
+    assert_eq!(escaped("'\\n'"), "\\n");
+    assert_eq!(escaped("'\\t'"), "\\t");
+    assert_eq!(escaped("'\\''"), "\\'");
+    assert_eq!(escaped("'\"'"), "\\\"");
+    assert_eq!(escaped("'\\\\'"), "\\\\");
+    assert_eq!(escaped("'\\x7f'"), "\\u{7f}");
+    assert_eq!(escaped("'\\u{1F600}'"), "\\u{1f600}");
+}
+
+#[test]
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+fn from_config_values() {
+    let span = Span::def_site();
+
+    match Lit::from_bool(true, span) {
+        Lit::Bool(lit) => assert!(lit.value),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_i64(42, span).lit {
+        Lit::Int(lit) => {
+            assert_eq!(lit.value(), 42);
+            assert_eq!(lit.suffix(), IntSuffix::None);
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_f64(1.0, span).lit {
+        Lit::Float(lit) => {
+            assert_eq!(lit.value(), 1.0);
+            assert_eq!(lit.suffix(), FloatSuffix::None);
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_string("a\"b", span) {
+        Lit::Str(lit) => assert_eq!(lit.value(), "a\"b"),
+        wrong => panic!("{:?}", wrong),
+    }
+
+    fn printed<T: ToTokens>(lit: T) -> String {
+        lit.into_tokens().to_string()
+    }
+    assert_eq!(printed(Lit::from_bool(false, span)), "false");
+    assert_eq!(printed(Lit::from_i64(i64::max_value(), span)), "9223372036854775807");
+    assert_eq!(printed(Lit::from_i64(-5, span)), "- 5");
+    assert_eq!(printed(Lit::from_f64(2.5, span)), "2.5");
+    assert_eq!(printed(Lit::from_f64(-2.5, span)), "- 2.5");
+    assert_eq!(printed(Lit::from_f64(-0.0, span)), "0.0");
+    assert_eq!(printed(Lit::from_string("x", span)), "\"x\"");
+
+    // Negative values print as a `-` and a literal, which parse back.
+    fn reparsed(signed: syn::SignedLit) -> syn::SignedLit {
+        let tokens: TokenStream = signed.into_tokens().into();
+        Lit::parse_maybe_negative.parse2(tokens).unwrap()
+    }
+    for &value in &[0, 5, -5, i64::max_value(), i64::min_value()] {
+        let signed = reparsed(Lit::from_i64(value, span));
+        assert_eq!(signed.negative, value < 0, "{}", value);
+        match signed.lit {
+            Lit::Int(lit) => assert_eq!(lit.value(), value.wrapping_abs() as u64),
+            wrong => panic!("{:?}", wrong),
+        }
+    }
+    for &value in &[2.5, -2.5, -1e300] {
+        let signed = reparsed(Lit::from_f64(value, span));
+        let magnitude = match signed.lit {
+            Lit::Float(lit) => lit.value(),
+            wrong => panic!("{:?}", wrong),
+        };
+        let value_back = if signed.negative { -magnitude } else { magnitude };
+        assert_eq!(value_back, value);
+    }
+}
+
+#[test]
+fn unbalanced_raw_string() {
+    fn str_error(s: &str) -> String {
+        lit_str(s).try_value().unwrap_err().to_string()
+    }
+    fn byte_str_error(s: &str) -> String {
+        lit_byte_str(s).try_value().unwrap_err().to_string()
+    }
+
+    assert_eq!(
+        str_error("r#\"a\""),
+        "unbalanced raw string pounds: expected 1 `#`, found 0"
+    );
+    assert_eq!(
+        str_error("r\"a\"#"),
+        "unbalanced raw string pounds: expected 0 `#`, found 1"
+    );
+    assert_eq!(
+        str_error("r##\"a\"#"),
+        "unbalanced raw string pounds: expected 2 `#`, found 1"
+    );
+    assert_eq!(
+        str_error("r#\"#"),
+        "unterminated raw string literal: expected `\"` followed by 1 `#`"
+    );
+    assert_eq!(
+        byte_str_error("br#\"a\"##"),
+        "unbalanced raw string pounds: expected 1 `#`, found 2"
+    );
+
+    fn str_raw_pounds(s: &str) -> Option<(usize, usize)> {
+        let lit = lit_str(s);
+        let error = lit.try_value().unwrap_err();
+        assert_eq!(lit.validate().unwrap_err().raw_pounds(), error.raw_pounds());
+        error.raw_pounds()
+    }
+    assert_eq!(str_raw_pounds("r##\"a\"#"), Some((2, 1)));
+    assert_eq!(str_raw_pounds("r#\"a\""), Some((1, 0)));
+    assert_eq!(str_raw_pounds("r#\"#"), Some((1, 0)));
+    assert_eq!(str_raw_pounds("\"\\q\""), None);
+    assert_eq!(lit_byte_str("br#\"a\"##").try_value().unwrap_err().raw_pounds(), Some((1, 2)));
+
+    assert_eq!(lit_str("r#\"a\"#").try_value().unwrap(), "a");
+    assert_eq!(lit_byte_str("br##\"a\"#\"##").try_value().unwrap(), b"a\"#");
+}
+
+#[test]
+#[should_panic(expected = "unbalanced raw string pounds")]
+fn unbalanced_raw_string_value_panics() {
+    lit_str("r#\"a\"").value();
+}
+
+#[test]
+fn truncated_and_non_ascii_escapes() {
+    fn error(s: &str) -> String {
+        let lit = lit_unchecked(s);
+        assert!(lit.validate().is_err(), "{}", s);
+        let error = match lit {
+            Lit::Str(lit) => lit.try_value().unwrap_err(),
+            Lit::Byte(lit) => lit.try_value().unwrap_err(),
+            Lit::Char(lit) => lit.try_value().unwrap_err(),
+            wrong => panic!("{:?}", wrong),
+        };
+        error.to_string()
+    }
+
+    assert_eq!(error("\"abc\\"), "unterminated string literal");
+    assert_eq!(error("\"\\"), "unterminated string literal");
+    assert_eq!(
+        error("\"\\é\""),
+        "unexpected character 'é' after \\ character in string literal"
+    );
+    assert_eq!(error("'\\"), "unterminated character literal");
+    assert_eq!(
+        error("'\\é'"),
+        "unexpected character 'é' after \\ character in character literal"
+    );
+    assert_eq!(error("b'\\"), "unterminated byte literal");
+    assert_eq!(error("\"\\x80\""), "Invalid \\x byte in string literal");
+    assert_eq!(error("'\\x80'"), "Invalid \\x byte in character literal");
+
+    let tokens = TokenStream::from(lit_unchecked("\"abc\\").into_tokens());
+    assert_eq!(Lit::validate_all(&tokens).len(), 1);
+}
+
+#[test]
+fn parse_str_with_trivia() {
+    let (lit, trivia) = Lit::parse_str_with_trivia("  42").unwrap();
+    match lit {
+        Lit::Int(lit) => assert_eq!(lit.value(), 42),
+        wrong => panic!("{:?}", wrong),
+    }
+    assert_eq!(trivia, "  ");
+
+    let (lit, trivia) = Lit::parse_str_with_trivia("\n\t\"s\"").unwrap();
+    match lit {
+        Lit::Str(lit) => assert_eq!(lit.value(), "s"),
+        wrong => panic!("{:?}", wrong),
+    }
+    assert_eq!(trivia, "\n\t");
+
+    let (_, trivia) = Lit::parse_str_with_trivia("true").unwrap();
+    assert_eq!(trivia, "");
+
+    let (lit, trivia) = Lit::parse_str_with_trivia("  /* c /* nested */ */ // line\n 'x'").unwrap();
+    match lit {
+        Lit::Char(lit) => assert_eq!(lit.value(), 'x'),
+        wrong => panic!("{:?}", wrong),
+    }
+    assert_eq!(trivia, "  /* c /* nested */ */ // line\n ");
+
+    let (_, trivia) = Lit::parse_str_with_trivia("/**/ //// four\n1").unwrap();
+    assert_eq!(trivia, "/**/ //// four\n");
+
+    fn error(s: &str) -> String {
+        Lit::parse_str_with_trivia(s).unwrap_err().to_string()
+    }
+    assert_eq!(error("  "), "expected literal");
+    assert_eq!(error("// only a comment"), "expected literal");
+    assert_eq!(error("  /// doc"), "expected literal, found doc comment");
+    assert_eq!(error("/** doc */ 1"), "expected literal, found doc comment");
+    assert_eq!(error("/* open 1"), "unterminated block comment");
+    assert!(Lit::parse_str_with_trivia(" ident").is_err());
+    assert!(Lit::parse_str_with_trivia(" 1 2").is_err());
+}
+
+#[test]
+fn sort_by_value() {
+    let mut lits: Vec<Lit> = [
+        "true", "2.5", "'b'", "10", "b\"z\"", "\"b\"", "false", "0x2", "1u8", "b'a'", "1",
+        "r\"a\"", "\"a\"", "1e0", "'a'", "b\"y\"",
+    ].iter()
+        .map(|s| syn::parse_str(s).unwrap())
+        .collect();
+    lits.push(lit("18446744073709551616"));
+    lits.sort_by(Lit::cmp_value);
+
+    let sorted: Vec<String> = lits.iter()
+        .map(|lit| lit.clone().into_tokens().to_string())
+        .collect();
+    assert_eq!(
+        sorted,
+        [
+            "\"a\"", "r\"a\"", "\"b\"", "b\"y\"", "b\"z\"", "b'a'", "'a'", "'b'", "1", "1u8",
+            "0x2", "10", "1e0", "2.5", "false", "true", "18446744073709551616",
+        ]
+    );
+
+    let kinds: Vec<syn::LitKind> = lits.iter().map(Lit::kind).collect();
+    let mut sorted_kinds = kinds.clone();
+    sorted_kinds.sort();
+    assert_eq!(kinds, sorted_kinds);
+}
+
+#[test]
+fn zero_and_empty() {
+    for s in &["0", "0.0", "0x0", "0b0_0", "0u8", "0e5", "b'\\0'", "'\\0'", "'\\x00'", "false"] {
+        assert!(syn::parse_str::<Lit>(s).unwrap().is_zero(), "{}", s);
+    }
+    for s in &["1", "0.5", "0x10", "b'0'", "'0'", "true", "\"\"", "b\"\""] {
+        assert!(!syn::parse_str::<Lit>(s).unwrap().is_zero(), "{}", s);
+    }
+    assert!(Lit::Float(syn::LitFloat::new(-0.0, FloatSuffix::None, Span::def_site())).is_zero());
+
+    fn str_is_empty(s: &str) -> bool {
+        lit_str(s).is_empty()
+    }
+    assert!(str_is_empty("\"\""));
+    assert!(str_is_empty("r#\"\"#"));
+    assert!(str_is_empty("\"\\\n    \""));
+    assert!(!str_is_empty("\"a\""));
+    assert!(!str_is_empty("\"\\0\""));
+    assert!(!str_is_empty("r\" \""));
+
+    fn byte_str_is_empty(s: &str) -> bool {
+        lit_byte_str(s).is_empty()
+    }
+    assert!(byte_str_is_empty("b\"\""));
+    assert!(byte_str_is_empty("br\"\""));
+    assert!(!byte_str_is_empty("b\"\\x00\""));
+}
+
+#[test]
+fn suffix_str() {
+    fn int_suffix(s: &str) -> String {
+        lit_int(s).suffix_str().to_owned()
+    }
+    assert_eq!(int_suffix("1u8"), "u8");
+    assert_eq!(int_suffix("1_i128"), "i128");
+    assert_eq!(int_suffix("0xffusize"), "usize");
+    assert_eq!(int_suffix("0b1010"), "");
+    assert_eq!(int_suffix("1"), "");
+
+    fn float_suffix(s: &str) -> String {
+        lit_float(s).suffix_str().to_owned()
+    }
+    assert_eq!(float_suffix("1.0f32"), "f32");
+    assert_eq!(float_suffix("1e10_f64"), "f64");
+    assert_eq!(float_suffix("1f64"), "f64");
+    assert_eq!(float_suffix("1.5"), "");
+    assert_eq!(float_suffix("2E-3"), "");
+}
+
+#[test]
+fn byte_string_hex_escape() {
+    fn decode(s: &str) -> Result<Vec<u8>, String> {
+        lit_byte_str(s).try_value().map_err(|err| err.to_string())
+    }
+
+    assert_eq!(decode("b\"\\x41\"").unwrap(), b"A");
+    assert_eq!(decode("b\"\\xfF\"").unwrap(), b"\xff");
+    assert_eq!(decode("b\"\\x1\"").unwrap_err(), "expected two hex digits after \\x");
+    assert_eq!(decode("b\"\\x\"").unwrap_err(), "expected two hex digits after \\x");
+    assert_eq!(decode("b\"\\x1g\"").unwrap_err(), "expected two hex digits after \\x");
+    assert!(decode("b\"\\").is_err());
+}
+
+#[test]
+fn ascii_only_source() {
+    for s in &["\"caf\\u{e9}\"", "'\\u{e9}'", "b\"\\xff\"", "1u8", "1.0", "true"] {
+        assert!(syn::parse_str::<Lit>(s).unwrap().is_ascii_only_source(), "{}", s);
+    }
+    for s in &["\"café\"", "r\"é\"", "'é'"] {
+        assert!(!syn::parse_str::<Lit>(s).unwrap().is_ascii_only_source(), "{}", s);
+    }
+    assert!(lit("18446744073709551616").is_ascii_only_source());
+}
+
+#[test]
+fn int_parse_ascribed() {
+    let parse = |s: &str| syn::LitInt::parse_ascribed.parse_str(s);
+
+    let lit = parse("5: u8").unwrap();
+    assert_eq!(lit.value(), 5);
+    assert_eq!(lit.suffix(), IntSuffix::U8);
+    assert_eq!(lit.into_tokens().to_string(), "5u8");
+
+    let lit = parse("0xff_ff: i128").unwrap();
+    assert_eq!(lit.value(), 0xffff);
+    assert_eq!(lit.suffix(), IntSuffix::I128);
+
+    let lit = parse("7").unwrap();
+    assert_eq!(lit.suffix(), IntSuffix::None);
+    let lit = parse("7usize").unwrap();
+    assert_eq!(lit.suffix(), IntSuffix::Usize);
+
+    let err = parse("5: f32").unwrap_err();
+    assert_eq!(err.to_string(), "expected integer type after `:`, found `f32`");
+    let err = parse("5: String").unwrap_err();
+    assert_eq!(err.to_string(), "expected integer type after `:`, found `String`");
+    let err = parse("5: 8").unwrap_err();
+    assert_eq!(err.to_string(), "expected integer type after `:`");
+    let err = parse("5u16: u8").unwrap_err();
+    assert_eq!(err.to_string(), "integer literal `5u16` already has a suffix");
+    assert!(parse("\"5\": u8").is_err());
+}
+
+#[test]
+fn int_unknown_suffix_str() {
+    for &(s, suffix) in &[("1u256", "u256"), ("0xffi256", "i256"), ("7_usize2", "usize2")] {
+        let lit = lit_int(s);
+        assert_eq!(lit.suffix_str(), suffix);
+        assert_eq!(lit.suffix(), IntSuffix::None);
+    }
+}
+
+#[test]
+fn suffixed_and_numeric() {
+    for s in &["5u8", "0xffi64", "1_usize", "1.0f32", "1e3_f64", "2f64"] {
+        let lit = syn::parse_str::<Lit>(s).unwrap();
+        assert!(lit.is_numeric(), "{}", s);
+        assert!(lit.is_suffixed(), "{}", s);
+    }
+    for s in &["5", "0xff", "1.0", "1e3"] {
+        let lit = syn::parse_str::<Lit>(s).unwrap();
+        assert!(lit.is_numeric(), "{}", s);
+        assert!(!lit.is_suffixed(), "{}", s);
+    }
+    for s in &["\"5u8\"", "b'5'", "'5'", "true"] {
+        let lit = syn::parse_str::<Lit>(s).unwrap();
+        assert!(!lit.is_numeric(), "{}", s);
+        assert!(!lit.is_suffixed(), "{}", s);
+    }
+}
+
+#[test]
+fn token_source() {
+    let lit = lit_str("\"a\\x41\"");
+    assert_eq!(lit.token_str(), "\"a\\x41\"");
+    assert_eq!(lit.value(), "aA");
+    let lit = lit_byte_str("br#\"a\\n\"#");
+    assert_eq!(lit.token_bytes(), b"br#\"a\\n\"#".to_vec());
+    assert_eq!(lit.value(), b"a\\n".to_vec());
+}
+
+#[test]
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+fn from_json_scalar() {
+    use syn::Scalar;
+    let span = Span::def_site();
+
+    match Lit::from_json_scalar(&Scalar::String("a\nb".to_owned()), span).unwrap().lit {
+        Lit::Str(lit) => assert_eq!(lit.value(), "a\nb"),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_json_scalar(&Scalar::Int(7), span).unwrap().lit {
+        Lit::Int(lit) => {
+            assert_eq!(lit.value(), 7);
+            assert_eq!(lit.suffix(), IntSuffix::None);
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    let negative = Lit::from_json_scalar(&Scalar::Int(-7), span).unwrap();
+    assert_eq!(negative.into_tokens().to_string(), "- 7");
+    match Lit::from_json_scalar(&Scalar::Float(0.25), span).unwrap().lit {
+        Lit::Float(lit) => {
+            assert_eq!(lit.value(), 0.25);
+            assert_eq!(lit.suffix(), FloatSuffix::None);
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_json_scalar(&Scalar::Bool(true), span).unwrap().lit {
+        Lit::Bool(lit) => assert!(lit.value),
+        wrong => panic!("{:?}", wrong),
+    }
+
+    let err = Lit::from_json_scalar(&Scalar::Null, span).unwrap_err();
+    assert_eq!(err.to_string(), "JSON null cannot be represented as a literal");
+}
+
+#[test]
+fn str_strip_prefix() {
+    fn strip(s: &str, prefix: &str) -> Option<String> {
+        lit_str(s)
+            .strip_prefix(prefix)
+            .map(|lit| lit.into_tokens().to_string())
+    }
+
+    assert_eq!(strip("\"prefix_name\"", "prefix_").unwrap(), "\"name\"");
+    assert_eq!(strip("\"\\x61b\"", "a").unwrap(), "\"b\"");
+    assert_eq!(strip("\"a\\\"b\"", "a").unwrap(), "\"\\\"b\"");
+    assert_eq!(strip("r#\"x\"y\"#", "x").unwrap(), "r#\"\"y\"#");
+    assert_eq!(strip("r\"xy\"", "xy").unwrap(), "r\"\"");
+    assert_eq!(strip("\"name\"", "").unwrap(), "\"name\"");
+    assert_eq!(strip("\"name\"", "prefix_"), None);
+
+    assert_eq!(lit_str("\"prefix_name\"").strip_prefix("prefix_").unwrap().value(), "name");
+}
+
+#[test]
+fn bool_typo_error() {
+    for &(s, suggestion) in &[
+        ("on", "true"),
+        ("yes", "true"),
+        ("True", "true"),
+        ("off", "false"),
+        ("no", "false"),
+        ("False", "false"),
+    ] {
+        let err = syn::parse_str::<Lit>(s).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "failed to parse literal: `{}` is not a boolean literal; use `{}`",
+                s, suggestion
+            )
+        );
+        assert!(syn::parse_str::<syn::LitBool>(s).is_err());
+    }
+
+    assert_eq!(
+        syn::parse_str::<Lit>("maybe").unwrap_err().to_string(),
+        "failed to parse literal: failed to parse"
+    );
+    match syn::parse_str::<Lit>("true").unwrap() {
+        Lit::Bool(lit) => assert!(lit.value),
+        wrong => panic!("{:?}", wrong),
+    }
+}
+
+#[test]
+fn str_value_chars() {
+    fn chars(s: &str) -> Vec<char> {
+        let lit = lit_str(s);
+        let chars = lit.value_chars();
+        assert_eq!(chars.iter().cloned().collect::<String>(), lit.value());
+        chars
+    }
+
+    assert_eq!(chars("\"a\\u{1F600}b\""), vec!['a', '\u{1F600}', 'b']);
+    assert_eq!(chars("\"é\\n\\x41\""), vec!['é', '\n', 'A']);
+    assert_eq!(chars("\"a\\\n   b\""), vec!['a', 'b']);
+    assert_eq!(chars("r#\"\\u{1F600}\"#"), "\\u{1F600}".chars().collect::<Vec<_>>());
+    assert_eq!(chars("\"\""), Vec::<char>::new());
+}
+
+#[test]
+fn token_ref() {
+    for s in &["\"a\"", "b\"a\"", "b'a'", "'a'", "1u8", "1.0", "r##\"raw\"##"] {
+        let lit = lit(s);
+        assert_eq!(lit.token_ref().unwrap().to_string(), *s);
+    }
+    let big = lit("18446744073709551616");
+    assert_eq!(big.token_ref().unwrap().to_string(), "18446744073709551616");
+    assert!(syn::parse_str::<Lit>("true").unwrap().token_ref().is_none());
+}
+
+#[test]
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+fn float_trailing_dot() {
+    let lit = lit_float("1.");
+    assert_eq!(lit.value(), 1.0);
+    assert!(lit.has_trailing_dot());
+    assert!(lit_float("1_000.").has_trailing_dot());
+
+    assert!(!lit_float("1.0").has_trailing_dot());
+    assert!(!lit_float("1e5").has_trailing_dot());
+    assert!(!lit_float("1f64").has_trailing_dot());
+}
+
+#[test]
+fn int_map_value_preserves_radix() {
+    fn render<T: Into<Option<syn::LitInt>>>(lit: T) -> String {
+        lit.into().unwrap().into_tokens().to_string()
+    }
+
+    assert_eq!(lit_int("0xFF").radix(), 16);
+    assert_eq!(lit_int("0o17").radix(), 8);
+    assert_eq!(lit_int("0b1").radix(), 2);
+    assert_eq!(lit_int("17").radix(), 10);
+    assert!(lit_int("0xFFu8").hex_is_uppercase());
+    assert!(!lit_int("0xffu8").hex_is_uppercase());
+    assert!(!lit_int("0x10").hex_is_uppercase());
+    assert!(!lit_int("10").hex_is_uppercase());
+
+    assert_eq!(render(lit_int("0xFF").map_value(true, |v| v + 1)), "0x100");
+    assert_eq!(render(lit_int("0xAB").map_value(true, |v| v + 1)), "0xAC");
+    assert_eq!(render(lit_int("0xab_u16").map_value(true, |v| v + 1)), "0xacu16");
+    assert_eq!(render(lit_int("0o17").map_value(true, |v| v * 2)), "0o36");
+    assert_eq!(render(lit_int("0b101u8").map_value(true, |v| v << 1)), "0b1010u8");
+    assert_eq!(render(lit_int("1_000").map_value(true, |v| v + 1)), "1001");
+    assert_eq!(render(lit_int("0xFF").map_value(false, |v| v)), "255");
+    assert_eq!(render(lit_int("0xffi32").map_value(false, |v| v)), "255i32");
+    assert!(lit_int("0xFFu8").map_value(true, |v| v + 1).is_none());
+    assert!(lit_int("127i8").map_value(false, |v| v + 1).is_none());
+    assert_eq!(render(lit_int("0xFFu16").map_value(true, |v| v + 1)), "0x100u16");
+    assert!(lit_int("1ms").map_value(false, |v| v + 1).is_none());
+
+    let sum = lit_int("0xF0").checked_add(&lit_int("0x0Fu8")).unwrap();
+    assert_eq!(render(sum.clone()), "0xFFu8");
+    assert_eq!(render(sum.map_value(false, |v| v)), "255u8");
+    assert_eq!(render(lit_int("0b11").checked_mul(&lit_int("3")).unwrap()), "0b1001");
+}
+
+#[test]
+fn parse_maybe_negative() {
+    let parse = |s: &str| Lit::parse_maybe_negative.parse_str(s);
+
+    let signed = parse("-5").unwrap();
+    assert!(signed.negative);
+    assert_eq!(signed.lit, lit("5"));
+
+    let signed = parse("10u8").unwrap();
+    assert!(!signed.negative);
+    assert_eq!(signed.lit, lit("10u8"));
+
+    let signed = parse("-170141183460469231731687303715884105728").unwrap();
+    assert!(signed.negative);
+    assert_eq!(signed.lit.kind(), syn::LitKind::Verbatim);
+
+    let signed = parse("-2.5").unwrap();
+    assert!(signed.negative);
+    assert_eq!(signed.lit, lit("2.5"));
+    assert!(!parse("\"s\"").unwrap().negative);
+
+    for s in &["-\"s\"", "-'c'", "-true", "-"] {
+        let err = parse(s).unwrap_err();
+        assert_eq!(err.to_string(), "expected number after `-`", "{}", s);
+    }
+}
+
+#[test]
+#[cfg(feature = "i128")]
+fn signed_to_i128() {
+    let parse = |s: &str| Lit::parse_maybe_negative.parse_str(s).unwrap().to_i128();
+
+    assert_eq!(parse("-5"), Some(-5));
+    assert_eq!(parse("10u8"), Some(10));
+    assert_eq!(parse("-0x10"), Some(-16));
+    assert_eq!(
+        parse("-170141183460469231731687303715884105728"),
+        Some(i128::min_value())
+    );
+    assert_eq!(
+        parse("170141183460469231731687303715884105727"),
+        Some(i128::max_value())
+    );
+    assert_eq!(parse("170141183460469231731687303715884105728"), None);
+    assert_eq!(parse("-2.5"), None);
+    assert_eq!(parse("\"s\""), None);
+
+    let span = Span::def_site();
+    for &value in &[0, 5, -5, i64::max_value(), i64::min_value()] {
+        assert_eq!(Lit::from_i64(value, span).to_i128(), Some(i128::from(value)), "{}", value);
+    }
+    let negative = Lit::from_json_scalar(&syn::Scalar::Int(-7), span).unwrap();
+    assert_eq!(negative.to_i128(), Some(-7));
+}
+
+#[test]
+fn byte_char_conversion() {
+    let c = lit_byte("b'A'").to_lit_char().unwrap();
+    assert_eq!(c.value(), 'A');
+    assert_eq!(c.to_lit_byte().unwrap().value(), b'A');
+
+    let b = lit_char("'A'").to_lit_byte().unwrap();
+    assert_eq!(b.value(), b'A');
+    assert_eq!(b.to_lit_char().unwrap().value(), 'A');
+
+    assert_eq!(lit_byte("b'\\n'").to_lit_char().unwrap().value(), '\n');
+    assert_eq!(lit_char("'\\x7f'").to_lit_byte().unwrap().value(), 0x7f);
+    assert!(lit_byte("b'\\xff'").to_lit_char().is_none());
+    assert!(lit_char("'é'").to_lit_byte().is_none());
+}
+
+#[test]
+fn empty_char_error() {
+    let lit = lit_char("''");
+    assert_eq!(lit.try_value().unwrap_err().to_string(), "empty character literal");
+    assert_eq!(lit_char("'\\''").try_value().unwrap(), '\'');
+}
+
+#[test]
+fn validate() {
+    fn validate(s: &str) -> Result<(), String> {
+        let result = match lit_unchecked(s) {
+            Lit::Str(lit) => {
+                assert_eq!(lit.validate().is_ok(), lit.try_value().is_ok());
+                lit.validate()
+            }
+            Lit::ByteStr(lit) => {
+                assert_eq!(lit.validate().is_ok(), lit.try_value().is_ok());
+                lit.validate()
+            }
+            Lit::Byte(lit) => lit.validate(),
+            Lit::Char(lit) => lit.validate(),
+            wrong => panic!("{:?}", wrong),
+        };
+        result.map_err(|err| err.to_string())
+    }
+
+    for s in &[
+        "\"a\\n\\t\\u{1F600}\\x41\"", "r#\"\\q\"#", "b\"\\xff\\0\"", "br\"\\q\"", "b'\\''",
+        "'\\u{10FFFF}'",
+    ] {
+        assert_eq!(validate(s), Ok(()), "{}", s);
+    }
+
+    assert_eq!(
+        validate("\"ok \\q\""),
+        Err("unexpected byte 113 after \\ character in string literal".to_owned())
+    );
+    assert_eq!(
+        validate("\"\\u{110000}\""),
+        Err("character code 110000 is not a valid unicode character".to_owned())
+    );
+    assert_eq!(validate("b\"\\x4\""), Err("expected two hex digits after \\x".to_owned()));
+    assert_eq!(
+        validate("\"a\"b\""),
+        Err("unexpected characters after end of string literal".to_owned())
+    );
+    assert_eq!(
+        validate("br#\"a\""),
+        Err("unbalanced raw string pounds: expected 1 `#`, found 0".to_owned())
+    );
+    assert_eq!(validate("''"), Err("empty character literal".to_owned()));
+    assert!(validate("b'\\q'").is_err());
+}
+
+#[test]
+fn source_byte_len() {
+    for s in &[
+        "\"a\"", "\"é\"", "r#\"x\"#", "b'\\n'", "'\\u{1F600}'", "0xFF_u8", "1.5e3", "true",
+    ] {
+        assert_eq!(syn::parse_str::<Lit>(s).unwrap().source_byte_len(), s.len(), "{}", s);
+    }
+    assert_eq!(syn::parse_str::<Lit>("false").unwrap().source_byte_len(), 5);
+    assert_eq!(lit("18446744073709551616").source_byte_len(), 20);
+}
+
+#[test]
+fn str_value_limited() {
+    fn limited(s: &str, max: usize) -> Result<String, String> {
+        lit_str(s).value_limited(max).map_err(|err| err.to_string())
+    }
+
+    assert_eq!(limited("\"abc\"", 3), Ok("abc".to_owned()));
+    assert_eq!(limited("r\"abc\"", 3), Ok("abc".to_owned()));
+    assert_eq!(limited("\"\\u{1F600}\"", 4), Ok("\u{1F600}".to_owned()));
+
+    let err = Err("string literal is longer than the limit of 3 bytes".to_owned());
+    assert_eq!(limited("\"abcd\"", 3), err);
+    assert_eq!(limited("r#\"abcd\"#", 3), err);
+    assert_eq!(limited("\"ab\\u{e9}\"", 3), err);
+
+    // The limit is hit before the invalid escape at the end is decoded.
+    let long = format!("\"{}\\q\"", "x".repeat(1 << 20));
+    assert_eq!(
+        limited(&long, 16),
+        Err("string literal is longer than the limit of 16 bytes".to_owned())
+    );
+    assert!(limited("\"a\\q\"", 16).is_err());
+}
+
+#[test]
+fn scalar_constructors() {
+    let span = Span::def_site();
+
+    match Lit::from_bool(false, span) {
+        Lit::Bool(lit) => assert!(!lit.value),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_char('\u{1F600}', span) {
+        Lit::Char(lit) => assert_eq!(lit.value(), '\u{1F600}'),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_byte(b'\n', span) {
+        Lit::Byte(lit) => assert_eq!(lit.value(), b'\n'),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_string("a\"b", span) {
+        Lit::Str(lit) => assert_eq!(lit.value(), "a\"b"),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_bytes(b"\x00\xff", span) {
+        Lit::ByteStr(lit) => assert_eq!(lit.value(), b"\x00\xff"),
+        wrong => panic!("{:?}", wrong),
+    }
+}
+
+#[test]
+fn decoded_len_guard() {
+    for s in &["\"\"", "\"abc\"", "\"a\\u{1F600}\\n\"", "r#\"a\\n\"#", "\"a\\\n   b\""] {
+        let expected = lit_str(s).value().len();
+        assert_eq!(lit(s).decoded_len(), Some(expected), "{}", s);
+    }
+    assert_eq!(lit("b\"a\\xff\"").decoded_len(), Some(2));
+    assert_eq!(lit("br\"\\xff\"").decoded_len(), Some(4));
+    assert_eq!(lit("'a'").decoded_len(), None);
+    assert_eq!(lit("1").decoded_len(), None);
+
+    // The guard and the limited decoder agree on where the limit lies, and the
+    // limited decoder stops at the limit instead of reaching the bad escape.
+    let big = lit_str(&format!("\"{}\\q\"", "y".repeat(64)));
+    let err = big.value_limited(8).unwrap_err();
+    assert_eq!(err.to_string(), "string literal is longer than the limit of 8 bytes");
+    let str_lit = lit_str("\"12345678\"");
+    assert_eq!(Lit::Str(str_lit.clone()).decoded_len(), Some(8));
+    assert_eq!(str_lit.value_limited(8).unwrap(), "12345678");
+    assert!(str_lit.value_limited(7).is_err());
+}
+
+#[test]
+fn bool_keyword_span() {
+    let tokens = TokenStream::from_str("false").unwrap();
+    let term_span = tokens.clone().into_iter().next().unwrap().span;
+    let lit = match syn::parse2::<Lit>(tokens).unwrap() {
+        Lit::Bool(lit) => lit,
+        wrong => panic!("{:?}", wrong),
+    };
+    assert!(!lit.value);
+    assert_eq!(format!("{:?}", lit.keyword_span()), format!("{:?}", term_span));
+    assert_eq!(format!("{:?}", lit.keyword_span()), format!("{:?}", lit.span));
+
+    let printed = lit.into_tokens().into_iter().next().unwrap();
+    assert_eq!(printed.to_string(), "false");
+    assert_eq!(format!("{:?}", printed.span), format!("{:?}", term_span));
+}
+
+#[test]
+fn float_missing_exponent_digits() {
+    let lit = lit_float("2.0ef64");
+    let err = lit.try_value().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected at least one digit in exponent of float literal `2.0ef64`"
+    );
+    assert!(lit_float("1.5e+").try_value().is_err());
+
+    for &(s, value) in &[("1e5f32", 1e5), ("2.0e3f64", 2.0e3), ("2.5E-1_f64", 0.25)] {
+        assert_eq!(lit_float(s).try_value().unwrap(), value);
+    }
+}
+
+#[test]
+fn str_replace() {
+    fn replace(s: &str, from: &str, to: &str) -> String {
+        lit_str(s).replace(from, to).into_tokens().to_string()
+    }
+
+    assert_eq!(replace("\"a_b_c\"", "_", "-"), "\"a-b-c\"");
+    assert_eq!(replace("\"a\\x5fb\"", "_", ""), "\"ab\"");
+    assert_eq!(replace("\"abc\"", "", "-"), "\"-a-b-c-\"");
+    assert_eq!(replace("r#\"a_b\"#", "_", "-"), "r#\"a-b\"#");
+    assert_eq!(replace("r\"a_b\"", "_", "\""), "r#\"a\"b\"#");
+    assert_eq!(replace("r#\"a_b\"#", "_", "\"#"), "r##\"a\"#b\"##");
+
+    let lit = lit_str("\"a_b_c\"");
+    let replaced = lit.replace("_", "-");
+    assert_eq!(replaced.value(), "a-b-c");
+    assert_eq!(format!("{:?}", replaced.span), format!("{:?}", lit.span));
+}
+
+#[test]
+fn coerce_text_and_bytes() {
+    fn to_byte_str(s: &str) -> Option<Vec<u8>> {
+        let lit = syn::parse_str::<Lit>(s).unwrap();
+        lit.coerce_to_byte_str(Span::call_site()).map(|lit| lit.value())
+    }
+    fn to_str(s: &str) -> Option<String> {
+        let lit = syn::parse_str::<Lit>(s).unwrap();
+        lit.coerce_to_str(Span::call_site()).map(|lit| lit.value())
+    }
+
+    assert_eq!(to_byte_str("\"é\"").unwrap(), b"\xc3\xa9");
+    assert_eq!(to_byte_str("'é'").unwrap(), b"\xc3\xa9");
+    assert_eq!(to_byte_str("b\"\\xff\"").unwrap(), b"\xff");
+    assert_eq!(to_byte_str("b'\\x80'").unwrap(), b"\x80");
+    assert_eq!(to_byte_str("1"), None);
+    assert_eq!(to_byte_str("1.0"), None);
+    assert_eq!(to_byte_str("true"), None);
+
+    assert_eq!(to_str("\"é\"").unwrap(), "é");
+    assert_eq!(to_str("'é'").unwrap(), "é");
+    assert_eq!(to_str("b\"\\xc3\\xa9\"").unwrap(), "é");
+    assert_eq!(to_str("b'a'").unwrap(), "a");
+    assert_eq!(to_str("b\"\\xff\""), None);
+    assert_eq!(to_str("b'\\x80'"), None);
+    assert_eq!(to_str("1"), None);
+    assert_eq!(to_str("false"), None);
+}
+
+#[test]
+fn to_tokens_spanned() {
+    let span = Span::call_site();
+    for s in &["\"s\"", "b\"s\"", "b's'", "'s'", "1u8", "1.5", "true", "false"] {
+        let lit = syn::parse_str::<Lit>(s).unwrap();
+        let mut tokens = quote::Tokens::new();
+        lit.to_tokens_spanned(&mut tokens, span);
+        let mut iter = tokens.into_iter();
+        let token = iter.next().unwrap();
+        assert!(iter.next().is_none());
+        assert_eq!(token.to_string(), *s);
+        assert_eq!(format!("{:?}", token.span), format!("{:?}", span));
+    }
+}
+
+#[test]
+fn lit_parse_str() {
+    match Lit::parse_str("0xffu8").unwrap() {
+        Lit::Int(lit) => {
+            assert_eq!(lit.value(), 255);
+            assert_eq!(lit.suffix(), IntSuffix::U8);
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::parse_str(" true ").unwrap() {
+        Lit::Bool(lit) => assert!(lit.value),
+        wrong => panic!("{:?}", wrong),
+    }
+
+    fn error(s: &str) -> String {
+        Lit::parse_str(s).unwrap_err().to_string()
+    }
+    assert_eq!(error("1 2"), "unexpected token `2` after literal");
+    assert_eq!(error("\"a\" + \"b\""), "unexpected token `+` after literal");
+    assert_eq!(error(""), "expected literal");
+    assert_eq!(error("ident"), "expected literal, found `ident`");
+    assert_eq!(error("yes"), "`yes` is not a boolean literal; use `true`");
+    assert_eq!(error("(1)"), "expected literal");
+    assert_eq!(error("\"unterminated"), "error while lexing literal");
+    assert_eq!(error("-1"), "`-` is not a literal");
+    assert_eq!(error("/// doc"), "expected literal, found doc comment");
+    assert_eq!(error("/** doc */"), "expected literal, found doc comment");
+}
+
+#[test]
+fn suffix_swap_keeps_spelling() {
+    assert_eq!(lit_float("1E10f64").unsuffixed().into_tokens().to_string(), "1E10");
+    assert_eq!(lit_float("1.5e+3_f32").unsuffixed().into_tokens().to_string(), "1.5e+3");
+    assert_eq!(lit_float("1f32").unsuffixed().into_tokens().to_string(), "1.0");
+    assert_eq!(lit_float("2.50").unsuffixed().into_tokens().to_string(), "2.50");
+    assert_eq!(
+        lit_float("1E10f64").with_suffix(FloatSuffix::F32).into_tokens().to_string(),
+        "1E10f32"
+    );
+    assert_eq!(
+        lit_float("1_000.0").with_suffix(FloatSuffix::F64).into_tokens().to_string(),
+        "1_000.0f64"
+    );
+    assert_eq!(lit_float("1.").with_suffix(FloatSuffix::F32).into_tokens().to_string(), "1.0f32");
+    assert_eq!(lit_float("1E10f64").with_suffix(FloatSuffix::None).value_str(), "1E10");
+    assert_eq!(lit_float("1E10f64").unsuffixed().suffix(), FloatSuffix::None);
+
+    assert_eq!(
+        lit_int("0xFF_u8").with_suffix(IntSuffix::U16).into_tokens().to_string(),
+        "0xFF_u16"
+    );
+    assert_eq!(lit_int("0xFF_u8").unsuffixed().into_tokens().to_string(), "0xFF");
+    assert_eq!(lit_int("1_000").with_suffix(IntSuffix::I64).into_tokens().to_string(), "1_000i64");
+    assert_eq!(lit_int("0o17u32").with_suffix(IntSuffix::None).value(), 15);
+}
+
+#[test]
+fn from_token_tree() {
+    let tokens: Vec<_> = TokenStream::from_str("\"s\" true false ident , (1)")
+        .unwrap()
+        .into_iter()
+        .collect();
+    match Lit::from_token_tree(&tokens[0]) {
+        Some(Lit::Str(lit)) => assert_eq!(lit.value(), "s"),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_token_tree(&tokens[1]) {
+        Some(Lit::Bool(lit)) => assert!(lit.value),
+        wrong => panic!("{:?}", wrong),
+    }
+    match Lit::from_token_tree(&tokens[2]) {
+        Some(Lit::Bool(lit)) => assert!(!lit.value),
+        wrong => panic!("{:?}", wrong),
+    }
+    assert!(Lit::from_token_tree(&tokens[3]).is_none());
+    assert!(Lit::from_token_tree(&tokens[4]).is_none());
+    assert!(Lit::from_token_tree(&tokens[5]).is_none());
+}
+
+#[test]
+fn char_codepoint() {
+    for &(s, codepoint, is_ascii) in &[
+        ("'a'", 0x61, true),
+        ("'\\u{7f}'", 0x7f, true),
+        ("'é'", 0xe9, false),
+        ("'\\u{FFFD}'", 0xfffd, false),
+        ("'😀'", 0x1f600, false),
+    ] {
+        let lit = lit_char(s);
+        assert_eq!(lit.codepoint(), codepoint, "{}", s);
+        assert_eq!(lit.is_ascii(), is_ascii, "{}", s);
+    }
+}
+
+#[test]
+fn int_to_radix_string() {
+    assert_eq!(lit_int("255").to_radix_string(16), "0xff");
+    assert_eq!(lit_int("255").to_radix_string(2), "0b11111111");
+    assert_eq!(lit_int("0xFF_u8").to_radix_string(8), "0o377");
+    assert_eq!(lit_int("0b1010").to_radix_string(10), "10");
+    assert_eq!(lit_int("35").to_radix_string(36), "z");
+    assert_eq!(lit_int("0").to_radix_string(16), "0x0");
+    assert_eq!(lit_int("18446744073709551615").to_radix_string(16), "0xffffffffffffffff");
+}
+
+#[test]
+#[should_panic(expected = "radix must be in the range 2 to 36")]
+fn int_to_radix_string_bad_radix() {
+    let lit = lit_int("1");
+    lit.to_radix_string(37);
+}
+
+#[test]
+fn str_uppercase_hex_escapes() {
+    fn uppercase(s: &str) -> bool {
+        lit_str(s).has_uppercase_hex_escapes()
+    }
+
+    assert!(uppercase("\"\\x7F\""));
+    assert!(uppercase("\"a\\u{1F600}b\""));
+    assert!(uppercase("\"\\u{fffD}\""));
+    assert!(!uppercase("\"\\x7f\""));
+    assert!(!uppercase("\"\\u{1f600}\""));
+    assert!(!uppercase("\"ABCDEF\""));
+    assert!(!uppercase("\"\\\\xFF\""));
+    assert!(!uppercase("r\"\\xFF\""));
+}
+
+#[test]
+fn float_with_value_preserving_format() {
+    fn with_value(s: &str, value: f64) -> Option<String> {
+        lit_float(s)
+            .with_value_preserving_format(value)
+            .map(|lit| lit.into_tokens().to_string())
+    }
+
+    // An unchanged value keeps the token exactly as written.
+    assert_eq!(with_value("1.0e6f64", 1e6).unwrap(), "1.0e6f64");
+    assert_eq!(with_value("1_000.000_f32", 1000.0).unwrap(), "1_000.000_f32");
+    assert_eq!(with_value("1E+3", 1000.0).unwrap(), "1E+3");
+
+    assert_eq!(with_value("1.0e6f64", 2e6).unwrap(), "2.0e6f64");
+    assert_eq!(with_value("1.0e6f64", 2.5e6).unwrap(), "2.5e6f64");
+    assert_eq!(with_value("1e-3", 2.5e-3).unwrap(), "2.5e-3");
+    assert_eq!(with_value("1E+3", 2000.0).unwrap(), "2E+3");
+    assert_eq!(with_value("1.5", 2.0).unwrap(), "2.0");
+    assert_eq!(with_value("1.5_f32", 0.1).unwrap(), "0.1_f32");
+    assert_eq!(with_value("1f64", 3.0).unwrap(), "3f64");
+    assert_eq!(with_value("1.5", 1e300).unwrap(), "1.0e300");
+    assert_eq!(with_value("1.5", 1e-7).unwrap(), "1.0e-7");
+    assert_eq!(with_value("1f64", 2.5e16).unwrap(), "2.5e16f64");
+    assert_eq!(with_value("1.5", 0.0).unwrap(), "0.0");
+    assert_eq!(with_value("1E+3", 1e300).unwrap(), "1E+300");
+
+    let lit = lit_float("1.0e6f64");
+    let new = lit.with_value_preserving_format(2.5e6).unwrap();
+    assert_eq!(new.value(), 2.5e6);
+    assert_eq!(new.suffix(), FloatSuffix::F64);
+
+    assert_eq!(with_value("1.0", -1.0), None);
+    assert_eq!(with_value("1.0", ::std::f64::INFINITY), None);
+    assert_eq!(with_value("1.0", ::std::f64::NAN), None);
+    assert_eq!(with_value("1.0f32", 1e40), None);
+    assert_eq!(with_value("1.0f64", 1e40).unwrap(), "1.0e40f64");
+}
+
+#[test]
+fn validate_all() {
+    // The lexer only produces well formed literals, so the bad ones are
+    // spliced in by hand.
+    let good: TokenStream = "const A: &str = \"a\"; fn f() { g(1u8, 'c', b\"x\", [2.5]) }"
+        .parse()
+        .unwrap();
+    assert!(Lit::validate_all(&good).is_empty());
+
+    let bad_str = TokenTree {
+        span: Span::call_site(),
+        kind: TokenNode::Literal(Literal::doccomment("r#\"a\"##")),
+    };
+    let bad_float = TokenTree {
+        span: Span::def_site(),
+        kind: TokenNode::Literal(Literal::doccomment("2.0ef64")),
+    };
+    let group = TokenTree {
+        span: Span::call_site(),
+        kind: TokenNode::Group(
+            Delimiter::Bracket,
+            vec![good.clone(), TokenStream::from(bad_float)].into_iter().collect(),
+        ),
+    };
+    let doc = TokenTree {
+        span: Span::call_site(),
+        kind: TokenNode::Literal(Literal::doccomment("/// docs")),
+    };
+    let stream: TokenStream = vec![
+        TokenStream::from(doc),
+        good.clone(),
+        TokenStream::from(bad_str),
+        TokenStream::from(group),
+    ].into_iter()
+        .collect();
+
+    let errors = Lit::validate_all(&stream);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors[0].to_string(),
+        "unbalanced raw string pounds: expected 1 `#`, found 2"
+    );
+    assert_eq!(format!("{:?}", errors[0].span()), format!("{:?}", Span::call_site()));
+    assert_eq!(
+        errors[1].to_string(),
+        "expected at least one digit in exponent of float literal `2.0ef64`"
+    );
+    assert_eq!(format!("{:?}", errors[1].span()), format!("{:?}", Span::def_site()));
+}
+
+#[test]
+fn str_new_cstr_safe() {
+    let span = Span::call_site();
+    let lit = syn::LitStr::new_cstr_safe("hello", span).unwrap();
+    assert_eq!(lit.value(), "hello");
+
+    let err = syn::LitStr::new_cstr_safe("hel\0lo", span).unwrap_err();
+    assert_eq!(err.to_string(), "string contains an interior nul byte at index 3");
+    assert!(syn::LitStr::new_cstr_safe("\0", span).is_err());
+    assert!(syn::LitStr::new_cstr_safe("", span).is_ok());
+}
+
+#[test]
+fn float_approx_eq() {
+    assert_eq!(lit("1.0").float_approx_eq(&lit("1.0000001"), 1e-6), Some(true));
+    assert_eq!(lit("1.0").float_approx_eq(&lit("1.0000001"), 1e-9), Some(false));
+    assert_eq!(lit("1.0f32").float_approx_eq(&lit("1e0"), 0.0), Some(true));
+    assert_eq!(lit("1.0").float_approx_eq(&lit("1"), 1.0), None);
+    assert_eq!(lit("\"1.0\"").float_approx_eq(&lit("1.0"), 1.0), None);
+}
+
+#[test]
+#[cfg(feature = "i128")]
+fn int_u128_constructor() {
+    fn int(value: u128, suffix: IntSuffix) -> Lit {
+        Lit::int_u128(value, suffix, Span::call_site())
+    }
+    let max = u128::from(u64::max_value());
+    assert_eq!(lit_int("0o777").value_u128(), 511);
+
+    match int(max, IntSuffix::None) {
+        Lit::Int(lit) => {
+            assert_eq!(lit.value(), u64::max_value());
+            assert_eq!(lit.suffix(), IntSuffix::None);
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match int(max, IntSuffix::U64) {
+        Lit::Int(lit) => assert_eq!(lit.into_tokens().to_string(), "18446744073709551615u64"),
+        wrong => panic!("{:?}", wrong),
+    }
+    match int(max, IntSuffix::U128) {
+        Lit::Verbatim(lit) => {
+            assert_eq!(lit.into_tokens().to_string(), "18446744073709551615u128");
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match int(5, IntSuffix::I128) {
+        Lit::Verbatim(lit) => assert_eq!(lit.into_tokens().to_string(), "5i128"),
+        wrong => panic!("{:?}", wrong),
+    }
+    match int(max + 1, IntSuffix::U128) {
+        Lit::Verbatim(lit) => {
+            assert_eq!(lit.into_tokens().to_string(), "18446744073709551616u128");
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match int(max + 1, IntSuffix::None) {
+        lit @ Lit::Verbatim(_) => {
+            assert_eq!(lit.into_tokens().to_string(), "18446744073709551616");
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+    match int(u128::max_value(), IntSuffix::I128) {
+        lit @ Lit::Verbatim(_) => {
+            let printed = lit.into_tokens().to_string();
+            assert_eq!(printed, format!("{}i128", u128::max_value()));
+            assert_eq!(lit_unchecked(&printed).kind(), syn::LitKind::Verbatim);
+        }
+        wrong => panic!("{:?}", wrong),
+    }
+}
+
+#[test]
+fn dedup_by_canonical_key() {
+    assert_eq!(lit("0xFF").canonical_key(), lit("255").canonical_key());
+    assert_eq!(lit("r\"a\"").canonical_key(), lit("\"\\x61\"").canonical_key());
+    assert_eq!(lit("1.0_f32").canonical_key(), lit("1e0f32").canonical_key());
+    assert_ne!(lit("255u8").canonical_key(), lit("255u16").canonical_key());
+    assert_ne!(lit("\"a\"").canonical_key(), lit("b\"a\"").canonical_key());
+    assert_ne!(lit("'a'").canonical_key(), lit("\"a\"").canonical_key());
+    assert!(lit("2").canonical_key() < lit("10").canonical_key());
+    assert_eq!(
+        lit("0x1_0000_0000_0000_0000").canonical_key(),
+        lit("18446744073709551616").canonical_key()
+    );
+
+    let mut lits = vec![lit("255u8"), lit("0xffu8"), lit("255u16")];
+    Lit::dedup(&mut lits);
+    let printed: Vec<_> = lits.iter().map(|lit| lit.clone().into_tokens().to_string()).collect();
+    assert_eq!(printed, ["255u16", "255u8"]);
+
+    let mut lits = vec![lit("\"b\""), lit("1"), lit("\"a\""), lit("r\"b\""), lit("0b1")];
+    Lit::dedup(&mut lits);
+    let printed: Vec<_> = lits.iter().map(|lit| lit.clone().into_tokens().to_string()).collect();
+    assert_eq!(printed, ["\"a\"", "\"b\"", "1"]);
+}
+
+#[test]
+fn str_raw_body() {
+    fn raw_body(s: &str) -> String {
+        lit_str(s).raw_body().to_owned()
+    }
+
+    assert_eq!(raw_body("\"a\\nb\""), "a\\nb");
+    assert_eq!(raw_body("\"\\u{1F600} \\\"q\\\"\""), "\\u{1F600} \\\"q\\\"");
+    assert_eq!(raw_body("\"\""), "");
+    assert_eq!(raw_body("r\"a\\nb\""), "a\\nb");
+    assert_eq!(raw_body("r##\"a\"#b\"##"), "a\"#b");
+}
+
+#[test]
+fn suffix_from_ident() {
+    assert_eq!(IntSuffix::from_ident("u64"), IntSuffix::U64);
+    assert_eq!(IntSuffix::from_ident("isize"), IntSuffix::Isize);
+    assert_eq!(IntSuffix::from_ident("px"), IntSuffix::None);
+    assert_eq!(IntSuffix::from_ident("U64"), IntSuffix::None);
+    assert_eq!(IntSuffix::from_ident(""), IntSuffix::None);
+
+    assert_eq!(FloatSuffix::from_ident("f32"), FloatSuffix::F32);
+    assert_eq!(FloatSuffix::from_ident("f64"), FloatSuffix::F64);
+    assert_eq!(FloatSuffix::from_ident("f16"), FloatSuffix::None);
+    assert_eq!(FloatSuffix::from_ident("px"), FloatSuffix::None);
+    assert_eq!(FloatSuffix::from_ident("f"), FloatSuffix::None);
+    assert_eq!(FloatSuffix::from_ident("f+32"), FloatSuffix::None);
+    assert_eq!(FloatSuffix::from_ident("foo"), FloatSuffix::None);
+}
+
+#[test]
+fn int_zero_forms() {
+    fn test_int(s: &str, value: u64, suffix: IntSuffix, radix: u32) {
+        let lit = lit_int(s);
+        assert_eq!(lit.value(), value, "{}", s);
+        assert_eq!(lit.suffix(), suffix, "{}", s);
+        assert_eq!(lit.radix(), radix, "{}", s);
+    }
+
+    use syn::IntSuffix::*;
+    test_int("0", 0, None, 10);
+    test_int("00", 0, None, 10);
+    test_int("0_0", 0, None, 10);
+    test_int("0x0", 0, None, 16);
+    test_int("0o0", 0, None, 8);
+    test_int("0b0", 0, None, 2);
+    test_int("0u8", 0, U8, 10);
+    test_int("0i128", 0, I128, 10);
+    test_int("0usize", 0, Usize, 10);
+    test_int("0_u8", 0, U8, 10);
+    test_int("00u16", 0, U16, 10);
+    test_int("0x0u8", 0, U8, 16);
+    test_int("0o0i32", 0, I32, 8);
+    test_int("0b0_u64", 0, U64, 2);
+    test_int("0xFFu64", 255, U64, 16);
+    test_int("0x0FF", 255, None, 16);
+    test_int("007", 7, None, 10);
+}
+
+#[test]
+fn str_starts_with_contains() {
+    let url = lit_str("\"\\x68ttp:\\u{2f}/example.com\\n\"");
+    assert!(url.starts_with("http://"));
+    assert!(url.starts_with(""));
+    assert!(!url.starts_with("https"));
+    assert!(url.contains("p://ex"));
+    assert!(url.contains(".com\n"));
+    assert!(url.contains(""));
+    assert!(!url.contains("com\\n"));
+
+    let short = lit_str("\"ab\"");
+    assert!(!short.starts_with("abc"));
+    assert!(!short.contains("abc"));
+    assert!(lit_str("\"aab\"").contains("ab"));
+
+    let raw = lit_str("r#\"http://\\x\"#");
+    assert!(raw.starts_with("http://"));
+    assert!(raw.contains("\\x"));
+    assert!(!raw.contains("\\n"));
+
+    // Scanning stops at the match, before reaching the malformed escape.
+    let lit = lit_str("\"http://\\q\"");
+    assert!(lit.starts_with("http"));
+    assert!(lit.contains("://"));
+}
+
+#[test]
+fn verbatim_int_decimal() {
+    fn decimal(s: &str) -> Option<String> {
+        lit_verbatim(s).int_decimal()
+    }
+
+    assert_eq!(
+        decimal("0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF").unwrap(),
+        "1329227995784915872903807060280344575"
+    );
+    assert_eq!(decimal("0x1_0000_0000_0000_0000u128").unwrap(), "18446744073709551616");
+    assert_eq!(
+        decimal("340282366920938463463374607431768211456").unwrap(),
+        "340282366920938463463374607431768211456"
+    );
+    assert_eq!(decimal("0o2000000000000000000000").unwrap(), "18446744073709551616");
+    assert_eq!(
+        decimal(&format!("0b1{}", "0".repeat(64))).unwrap(),
+        "18446744073709551616"
+    );
+    assert_eq!(decimal("0x0000_0001_0000_0000_0000_0000").unwrap(), "18446744073709551616");
+
+    // Verbatim tokens that are not integers at all.
+    assert_eq!(decimal("0x1p4"), None);
+    assert_eq!(decimal("0xAp+2f32"), None);
+
+    // Lit::new makes this a LitFloat, but nothing stops a caller from
+    // wrapping it in a LitVerbatim.
+    let overflow = syn::LitVerbatim {
+        token: Literal::doccomment("1e400"),
+        span: Span::def_site(),
+    };
+    assert_eq!(overflow.int_decimal(), None);
+}
+
+#[test]
+fn classification() {
+    use syn::LitKind::*;
+    for &(s, kind) in &[
+        ("0", Int),
+        ("7", Int),
+        ("10", Int),
+        ("7u8", Int),
+        ("0x7", Int),
+        ("1.0", Float),
+        ("1e3", Float),
+        ("1f32", Float),
+        ("18446744073709551616", Verbatim),
+        ("\"s\"", Str),
+        ("\"\"", Str),
+        ("r\"s\"", Str),
+        ("r#\"s\"#", Str),
+        ("b\"s\"", ByteStr),
+        ("br\"s\"", ByteStr),
+        ("b's'", Byte),
+        ("'s'", Char),
+        ("'\\''", Char),
+    ] {
+        assert_eq!(lit(s).kind(), kind, "{}", s);
+    }
+    assert_eq!(lit_unchecked("true").kind(), Bool);
+    assert_eq!(lit_unchecked("false").kind(), Bool);
+}
+
+#[test]
+fn peek_kind() {
+    use syn::buffer::TokenBuffer;
+    use syn::{LitBool, LitByte, LitByteStr, LitChar, LitFloat, LitInt, LitStr, LitVerbatim};
+
+    fn peeks(s: &str) -> Vec<&'static str> {
+        let buffer = TokenBuffer::new2(TokenStream::from_str(s).unwrap());
+        let cursor = buffer.begin();
+        let mut found = Vec::new();
+        for &(name, peek) in &[
+            ("Lit", Lit::peek as fn(_) -> bool),
+            ("LitStr", LitStr::peek),
+            ("LitByteStr", LitByteStr::peek),
+            ("LitByte", LitByte::peek),
+            ("LitChar", LitChar::peek),
+            ("LitInt", LitInt::peek),
+            ("LitFloat", LitFloat::peek),
+            ("LitBool", LitBool::peek),
+            ("LitVerbatim", LitVerbatim::peek),
+        ] {
+            if peek(cursor) {
+                found.push(name);
+            }
+        }
+        found
+    }
+
+    assert_eq!(peeks("\"s\" 1"), ["Lit", "LitStr"]);
+    assert_eq!(peeks("b\"s\""), ["Lit", "LitByteStr"]);
+    assert_eq!(peeks("b's'"), ["Lit", "LitByte"]);
+    assert_eq!(peeks("'s'"), ["Lit", "LitChar"]);
+    assert_eq!(peeks("1u8 \"s\""), ["Lit", "LitInt"]);
+    assert_eq!(peeks("1.5"), ["Lit", "LitFloat"]);
+    assert_eq!(peeks("true"), ["Lit", "LitBool"]);
+    assert_eq!(peeks("18446744073709551616"), ["Lit", "LitVerbatim"]);
+    assert!(peeks("ident").is_empty());
+    assert!(peeks("-1").is_empty());
+    assert!(peeks("(1)").is_empty());
+    assert!(peeks("").is_empty());
+}
+
+#[test]
+fn int_minimal_suffix() {
+    fn minimal(s: &str, signed: bool) -> IntSuffix {
+        lit_int(s).minimal_suffix(signed)
+    }
+
+    use syn::IntSuffix::*;
+    assert_eq!(minimal("0", false), U8);
+    assert_eq!(minimal("255", false), U8);
+    assert_eq!(minimal("256", false), U16);
+    assert_eq!(minimal("65536u64", false), U32);
+    assert_eq!(minimal("4294967296", false), U64);
+    assert_eq!(minimal("18446744073709551615", false), U64);
+
+    // The magnitude in `-1` is the literal `1`.
+    assert_eq!(minimal("1", true), I8);
+    assert_eq!(minimal("127", true), I8);
+    assert_eq!(minimal("128", true), I16);
+    assert_eq!(minimal("1000i64", true), I16);
+    assert_eq!(minimal("2147483648", true), I64);
+    assert_eq!(minimal("9223372036854775808", true), I128);
+
+    let lit = lit_int("1000i64");
+    let tight = lit.with_suffix(lit.minimal_suffix(true));
+    assert_eq!(tight.into_tokens().to_string(), "1000i16");
+}
+
+#[test]
+fn str_decoded_byte_and_char_len() {
+    fn lens(s: &str) -> (usize, usize) {
+        let lit = lit_str(s);
+        assert_eq!(lit.decoded_byte_len(), lit.value().len());
+        assert_eq!(lit.decoded_char_len(), lit.value().chars().count());
+        (lit.decoded_byte_len(), lit.decoded_char_len())
+    }
+
+    assert_eq!(lens("\"abc\""), (3, 3));
+    assert_eq!(lens("\"\""), (0, 0));
+    assert_eq!(lens("\"héllo\""), (6, 5));
+    assert_eq!(lens("\"\\u{1F600}\\n\""), (5, 2));
+    assert_eq!(lens("\"a\\\n    b\""), (2, 2));
+    assert_eq!(lens("r#\"日本\"#"), (6, 2));
+}
+
+#[test]
+fn escape_e_hint() {
+    let lit = lit_str("\"\\e[0m\"");
+    let err = lit.try_value().unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "unexpected byte 101 after \\ character in string literal; \
+         there is no \\e escape, use \\x1b for the escape character"
+    );
+    assert!(lit_char("'\\e'").try_value().unwrap_err().to_string().contains("\\x1b"));
+    assert!(lit_byte_str("b\"\\e\"").try_value().unwrap_err().to_string().contains("\\x1b"));
+    assert!(!lit_str("\"\\q\"").try_value().unwrap_err().to_string().contains("\\x1b"));
+}
+
+#[test]
+fn to_source_annotated() {
+    assert_eq!(lit("255u8").to_source_annotated("width"), "255u8 /* width */");
+    assert_eq!(
+        lit("r#\"a\\n\"#").to_source_annotated("from config.toml"),
+        "r#\"a\\n\"# /* from config.toml */"
+    );
+    assert_eq!(
+        syn::parse_str::<Lit>("true").unwrap().to_source_annotated("default"),
+        "true /* default */"
+    );
+    assert_eq!(lit("1.").to_source_annotated("a */ b"), "1. /* a * / b */");
+    assert_eq!(lit("5").to_source_annotated("see /* here"), "5 /* see / * here */");
+    assert_eq!(lit("5").to_source_annotated("a /*/ b"), "5 /* a / * / b */");
+}
+
+#[test]
+fn str_value_cstr_safe() {
+    fn cstr(s: &str) -> Result<String, String> {
+        lit_str(s).value_cstr_safe().map_err(|err| err.to_string())
+    }
+
+    assert_eq!(cstr("\"ab\"").unwrap(), "ab");
+    assert_eq!(cstr("\"a\\nb\"").unwrap(), "a\nb");
+    assert_eq!(cstr("r\"a\\0b\"").unwrap(), "a\\0b");
+    for s in &["\"a\\0b\"", "\"a\\x00b\"", "\"a\\u{0}b\"", "r\"a\0b\""] {
+        assert_eq!(cstr(s).unwrap_err(), "string literal contains a nul character", "{}", s);
+    }
+
+    // Decoding stops at the NUL before reaching the malformed escape.
+    assert_eq!(cstr("\"\\0\\q\"").unwrap_err(), "string literal contains a nul character");
+}
+
+#[test]
+fn normalized() {
+    fn normalized(s: &str) -> String {
+        let lit = lit(s);
+        let normalized = lit.normalized();
+        assert_eq!(normalized.canonical_key(), lit.canonical_key());
+        normalized.into_tokens().to_string()
+    }
+
+    assert_eq!(normalized("007"), "7");
+    assert_eq!(normalized("0_0"), "0");
+    assert_eq!(normalized("00_7_000u32"), "7_000u32");
+    assert_eq!(normalized("0"), "0");
+    assert_eq!(normalized("0u8"), "0u8");
+    assert_eq!(normalized("0xFF"), "0xff");
+    assert_eq!(normalized("0xABu8"), "0xabu8");
+    assert_eq!(normalized("0b0101"), "0b0101");
+    assert_eq!(normalized("1."), "1.0");
+    assert_eq!(normalized("1.5e3f32"), "1.5e3f32");
+    assert_eq!(normalized("1e+5"), "1e5");
+    assert_eq!(normalized("2.5E+10_f64"), "2.5E10_f64");
+    assert_eq!(normalized("1e-5"), "1e-5");
+    assert_eq!(normalized("\"\\x41\""), "\"\\x41\"");
+}
+
+#[test]
+fn str_matches_glob() {
+    fn matches(s: &str, pattern: &str) -> bool {
+        lit_str(s).matches_glob(pattern)
+    }
+
+    assert!(matches("\"foo.rs\"", "*.rs"));
+    assert!(!matches("\"foo.txt\"", "*.rs"));
+    assert!(matches("\"foo.rs\"", "f*"));
+    assert!(!matches("\"foo.rs\"", "*.r"));
+    assert!(matches("\"foo.rs\"", "f?o.??"));
+    assert!(!matches("\"foo.rs\"", "f?o.?"));
+    assert!(matches("\"foo.rs\"", "*"));
+    assert!(matches("\"\"", "*"));
+    assert!(!matches("\"\"", "?"));
+    assert!(matches("\"a.b.rs\"", "*.*.rs"));
+    assert!(matches("\"aaab\"", "*a*b"));
+    assert!(!matches("\"aaac\"", "*a*b"));
+    assert!(matches("\"caf\\u{e9}\"", "caf?"));
+    assert!(matches("\"x*y\"", "x*y"));
+}
+
+#[test]
+fn int_and_float_digits() {
+    for &(s, digits) in &[
+        ("0xFF_00u16", "FF_00"),
+        ("0b1010", "1010"),
+        ("0o7_7", "7_7"),
+        ("1_000", "1_000"),
+        ("1_u8", "1_"),
+        ("007i32", "007"),
+    ] {
+        assert_eq!(lit_int(s).digits(), digits);
+    }
+    for &(s, digits) in &[
+        ("1_000.5e3_f32", "1_000.5e3_"),
+        ("1.", "1."),
+        ("2.5E-3f64", "2.5E-3"),
+        ("1f32", "1"),
+    ] {
+        assert_eq!(lit_float(s).digits(), digits);
+    }
+}
+
+#[test]
+fn char_unicode_escape_case() {
+    let smile = lit_char("'\\u{1f600}'");
+    assert_eq!(smile.unicode_escape_uppercase(), Some(false));
+    let upper = smile.with_unicode_escape(true);
+    assert_eq!(upper.clone().into_tokens().to_string(), "'\\u{1F600}'");
+    assert_eq!(upper.unicode_escape_uppercase(), Some(true));
+    assert_eq!(upper.value(), '😀');
+    assert_eq!(upper.with_unicode_escape(false).into_tokens().to_string(), "'\\u{1f600}'");
+
+    assert_eq!(lit_char("'\\u{41}'").unicode_escape_uppercase(), Some(true));
+    assert_eq!(lit_char("'\\u{00aB}'").unicode_escape_uppercase(), Some(false));
+    assert_eq!(lit_char("'a'").unicode_escape_uppercase(), None);
+    assert_eq!(lit_char("'\\x7F'").unicode_escape_uppercase(), None);
+    assert_eq!(
+        lit_char("'a'").with_unicode_escape(true).into_tokens().to_string(),
+        "'\\u{61}'"
+    );
+}
+
+#[test]
+#[cfg(feature = "i128")]
+fn int_wrapping_value_i128() {
+    for &(s, value) in &[
+        ("200u8", 200),
+        ("200i8", -56),
+        ("255i8", -1),
+        ("256u8", 0),
+        ("127i8", 127),
+        ("65535i16", -1),
+        ("0xFFFF_FFFFi32", -1),
+        ("0xFFFF_FFFF_FFFF_FFFFi64", -1),
+        ("0xFFFF_FFFF_FFFF_FFFFisize", -1),
+        ("0xFFFF_FFFF_FFFF_FFFFu64", 0xFFFF_FFFF_FFFF_FFFF),
+        ("0xFFFF_FFFF_FFFF_FFFFi128", 0xFFFF_FFFF_FFFF_FFFF),
+        ("0xFFFF_FFFF_FFFF_FFFF", 0xFFFF_FFFF_FFFF_FFFF),
+        ("300", 300),
+    ] {
+        let lit = lit_int(s);
+        assert_eq!(lit.wrapping_value_i128(lit.suffix()), value, "{}", s);
+    }
+
+    for &(s, ref suffix, value) in &[
+        ("200", IntSuffix::I8, -56),
+        ("255", IntSuffix::I8, -1),
+        ("255", IntSuffix::U8, 255),
+        ("65536", IntSuffix::U16, 0),
+        ("255i8", IntSuffix::I16, 255),
+        ("300", IntSuffix::None, 300),
+    ] {
+        assert_eq!(lit_int(s).wrapping_value_i128(suffix.clone()), value, "{}", s);
+    }
+}
+
+#[test]
+fn parse_maybe_parenthesized() {
+    fn int(s: &str) -> u64 {
+        match Lit::parse_maybe_parenthesized.parse_str(s).unwrap() {
+            Lit::Int(lit) => lit.value(),
+            wrong => panic!("{:?}", wrong),
+        }
+    }
+
+    assert_eq!(int("42"), 42);
+    assert_eq!(int("(42)"), 42);
+    assert_eq!(int("( 0x2a )"), 42);
+
+    let err = Lit::parse_maybe_parenthesized.parse_str("(1 2)").unwrap_err();
+    assert!(err.to_string().contains("expected a single literal inside parentheses"));
+    assert!(Lit::parse_maybe_parenthesized.parse_str("((42))").is_err());
+    assert!(Lit::parse_maybe_parenthesized.parse_str("()").is_err());
+    assert!(Lit::parse_maybe_parenthesized.parse_str("[42]").is_err());
+    assert!(Lit::parse_maybe_parenthesized.parse_str("(42) 1").is_err());
+}
+
+#[test]
+fn split_unit() {
+    fn int(s: &str) -> (u64, String) {
+        let lit = lit_int(s);
+        let (value, unit) = lit.split_unit();
+        (value, unit.to_owned())
+    }
+    fn float(s: &str) -> (f64, String) {
+        let lit = lit_float(s);
+        let (value, unit) = lit.split_unit();
+        (value, unit.to_owned())
+    }
+
+    assert_eq!(int("1024MiB"), (1024, "MiB".to_owned()));
+    assert_eq!(int("5ms"), (5, "ms".to_owned()));
+    assert_eq!(int("10"), (10, "".to_owned()));
+    assert_eq!(int("1_000_u8"), (1000, "u8".to_owned()));
+
+    assert_eq!(float("5.0ms"), (5.0, "ms".to_owned()));
+    assert_eq!(float("1.5em"), (1.5, "em".to_owned()));
+    assert_eq!(float("2.5e3Hz"), (2500.0, "Hz".to_owned()));
+    assert_eq!(float("1e-3s"), (0.001, "s".to_owned()));
+    assert_eq!(float("1.0f32"), (1.0, "f32".to_owned()));
+    assert_eq!(float("1_0.5"), (10.5, "".to_owned()));
+}
+
+#[test]
+fn float_unit_suffix() {
+    let cases = [
+        ("5.0ms", 5.0, "5.0", "5.0"),
+        ("1.5em", 1.5, "1.5", "1.5"),
+        ("2.5e3Hz", 2500.0, "2.5e3", "2500.0"),
+    ];
+    for &(s, value, value_str, escaped) in &cases {
+        let lit = lit_unchecked(s);
+        let float = match lit {
+            Lit::Float(ref float) => float.clone(),
+            _ => panic!("expected float for {}", s),
+        };
+        assert_eq!(float.value(), value, "{}", s);
+        assert_eq!(float.value_str(), value_str, "{}", s);
+        assert!(!lit.is_zero(), "{}", s);
+        assert!(lit.validate().is_ok(), "{}", s);
+        assert_eq!(lit.display_escaped(), escaped, "{}", s);
+        assert_eq!(lit.fingerprint(), lit.fingerprint());
+        assert!(lit.canonical_key() == lit.canonical_key());
+        assert_eq!(lit.cmp_value(&lit_unchecked(s)), std::cmp::Ordering::Equal, "{}", s);
+    }
+    assert!(lit_unchecked("0.0ms").is_zero());
+
+    let mut lits = vec![lit_unchecked("5.0ms"), lit_unchecked("5.0ms"), lit_unchecked("1.5em")];
+    Lit::dedup(&mut lits);
+    assert_eq!(lits.len(), 2);
+}
+
+#[test]
+fn str_find_duplicates() {
+    let lits = [
+        lit_str(r#""a""#),
+        lit_str(r#""b""#),
+        lit_str(r#"r"a""#),
+        lit_str(r#""\x62""#),
+        lit_str(r#""c""#),
+        lit_str(r##"r#"a"#"##),
+    ];
+    assert_eq!(syn::LitStr::find_duplicates(&lits), vec![(0, 2), (1, 3), (0, 5)]);
+    assert_eq!(syn::LitStr::find_duplicates(&lits[..2]), vec![]);
+}
+
+#[test]
+fn float_to_f32_checked() {
+    fn to_f32_checked(s: &str) -> Option<f32> {
+        lit_float(s).to_f32_checked()
+    }
+
+    assert_eq!(to_f32_checked("1e40f64"), None);
+    assert_eq!(to_f32_checked("1e40"), None);
+    assert_eq!(to_f32_checked("1.0"), Some(1.0));
+    assert_eq!(to_f32_checked("3.4e38"), Some(3.4e38));
+    assert_eq!(to_f32_checked("0.1f64"), Some(0.1));
+    assert_eq!(to_f32_checked("1e-50"), Some(0.0));
+}
+
+#[test]
+fn str_value_normalized() {
+    fn values(s: &str) -> (String, String) {
+        let lit = lit_str(s);
+        (lit.value(), lit.value_normalized())
+    }
+
+    assert_eq!(
+        values(r#""a\r\nb\rc\nd""#),
+        ("a\r\nb\rc\nd".to_owned(), "a\nb\nc\nd".to_owned())
+    );
+    assert_eq!(values("\"a\r\nb\""), ("a\nb".to_owned(), "a\nb".to_owned()));
+    assert_eq!(values(r#""\x0d""#), ("\r".to_owned(), "\n".to_owned()));
+    assert_eq!(values(r#""plain""#), ("plain".to_owned(), "plain".to_owned()));
+}
+
+#[test]
+fn source_byte_len_emitted_offsets() {
+    let lits = vec![
+        lit("\"a\\tb\""),
+        lit("r#\"raw\"#"),
+        lit("b\"\\xff\""),
+        lit("b'x'"),
+        lit("'\\u{1F600}'"),
+        lit("0xFF_u8"),
+        lit("1.5e10_f32"),
+        lit("340282366920938463463374607431768211455u128"),
+        syn::parse_str::<Lit>("true").unwrap(),
+        syn::parse_str::<Lit>("false").unwrap(),
+    ];
+
+    let mut source = String::new();
+    let mut offset = 0;
+    for lit in &lits {
+        assert_eq!(source.len(), offset);
+        let text = TokenStream::from(lit).to_string();
+        assert_eq!(lit.source_byte_len(), text.len(), "{}", text);
+        source.push_str(&text);
+        offset += lit.source_byte_len();
+    }
+    assert_eq!(lits.iter().map(Lit::source_byte_len).sum::<usize>(), source.len());
+}
+
+#[test]
+fn int_suffix_every_type() {
+    fn suffix(s: &str) -> IntSuffix {
+        lit_int(s).suffix()
+    }
+
+    let suffixes = [
+        ("i8", IntSuffix::I8),
+        ("i16", IntSuffix::I16),
+        ("i32", IntSuffix::I32),
+        ("i64", IntSuffix::I64),
+        ("i128", IntSuffix::I128),
+        ("isize", IntSuffix::Isize),
+        ("u8", IntSuffix::U8),
+        ("u16", IntSuffix::U16),
+        ("u32", IntSuffix::U32),
+        ("u64", IntSuffix::U64),
+        ("u128", IntSuffix::U128),
+        ("usize", IntSuffix::Usize),
+    ];
+    for &(text, ref expected) in &suffixes {
+        for digits in &["1", "1_", "0x7f", "0o17", "0b1", "1_000_"] {
+            let token = format!("{}{}", digits, text);
+            assert_eq!(suffix(&token), *expected, "{}", token);
+            if let Lit::Int(lit) = lit(&token) {
+                assert_eq!(lit.suffix(), *expected, "{}", token);
+            } else {
+                panic!("{}", token);
+            }
+        }
+    }
+
+    // Only an exact match names a type; a suffix that merely starts or ends
+    // with one does not.
+    for token in &["1i1281", "1i8x", "1xi8", "1size", "1u", "1i", "1u1", "1u1288"] {
+        assert_eq!(suffix(token), IntSuffix::None, "{}", token);
+    }
+}
+
+#[test]
+fn char_best() {
+    fn best(ch: char) -> String {
+        let best = syn::LitChar::best(ch, Span::def_site());
+        assert_eq!(best.value(), ch);
+        let text = TokenStream::from(Lit::Char(best)).to_string();
+        assert_eq!(lit_char(&text).value(), ch);
+        text
+    }
+
+    assert_eq!(best('a'), "'a'");
+    assert_eq!(best('"'), "'\"'");
+    assert_eq!(best('é'), "'é'");
+    assert_eq!(best('😀'), "'😀'");
+    assert_eq!(best(' '), "' '");
+    assert_eq!(best('\''), r"'\''");
+    assert_eq!(best('\\'), r"'\\'");
+    assert_eq!(best('\n'), r"'\n'");
+    assert_eq!(best('\r'), r"'\r'");
+    assert_eq!(best('\t'), r"'\t'");
+    assert_eq!(best('\0'), r"'\0'");
+    assert_eq!(best('\u{7f}'), r"'\u{7f}'");
+    assert_eq!(best('\u{1b}'), r"'\u{1b}'");
+    assert_eq!(best('\u{a0}'), r"'\u{a0}'");
+    assert_eq!(best('\u{200b}'), r"'\u{200b}'");
+    assert_eq!(best('\u{202e}'), r"'\u{202e}'");
+    assert_eq!(best('\u{ad}'), r"'\u{ad}'");
+    assert_eq!(best('\u{301}'), r"'\u{301}'");
+    assert_eq!(best('\u{20dd}'), r"'\u{20dd}'");
+    assert_eq!(best('\u{e000}'), r"'\u{e000}'");
+    assert_eq!(best('\u{10fffd}'), r"'\u{10fffd}'");
+    assert_eq!(best('\u{fffe}'), r"'\u{fffe}'");
+    assert_eq!(best('_'), "'_'");
+    assert_eq!(best('٣'), "'٣'");
+    assert_eq!(best('·'), "'·'");
+}
+
+#[test]
+fn int_fits_in() {
+    fn fits_in(s: &str, suffix: IntSuffix) -> bool {
+        lit_int(s).fits_in(suffix)
+    }
+
+    assert!(!fits_in("300u32", IntSuffix::U8));
+    assert!(fits_in("200u32", IntSuffix::U8));
+    assert!(!fits_in("200u32", IntSuffix::I8));
+    assert!(fits_in("127", IntSuffix::I8));
+    assert!(fits_in("0xffff", IntSuffix::U16));
+    assert!(!fits_in("0x1_0000", IntSuffix::U16));
+    assert!(!fits_in("0x8000_0000", IntSuffix::I32));
+    assert!(fits_in("0x8000_0000", IntSuffix::U32));
+    assert!(fits_in("0x7fff_ffff_ffff_ffff", IntSuffix::Isize));
+    assert!(!fits_in("0x8000_0000_0000_0000", IntSuffix::Isize));
+    assert!(fits_in("0xffff_ffff_ffff_ffff", IntSuffix::Usize));
+    assert!(fits_in("0xffff_ffff_ffff_ffff", IntSuffix::I128));
+    assert!(fits_in("0xffff_ffff_ffff_ffff", IntSuffix::None));
+}
+
+#[test]
+fn respanned_keeps_token() {
+    let span = Span::call_site();
+    let lits = vec![
+        lit("r#\"a\"#"),
+        lit("b\"\\x00\""),
+        lit("b'x'"),
+        lit("'\\u{1F600}'"),
+        lit("0xFF_u8"),
+        lit("1.5e10_f32"),
+        lit("340282366920938463463374607431768211455u128"),
+        syn::parse_str::<Lit>("true").unwrap(),
+    ];
+    for original in &lits {
+        let cloned = original.respanned(span);
+        assert_eq!(
+            TokenStream::from(&cloned).to_string(),
+            TokenStream::from(original).to_string()
+        );
+        assert_eq!(format!("{:?}", cloned.span()), format!("{:?}", span));
+        assert_eq!(cloned, *original);
+    }
+}
+
+#[test]
+fn quote_escapes_everywhere() {
+    // Rust accepts both `\'` and `\"` in every kind of quoted literal,
+    // whichever quote delimits it.
+    for &(s, expected) in &[(r#"'\"'"#, '"'), (r#"'\''"#, '\'')] {
+        let lit = lit_char(s);
+        assert_eq!(lit.value(), expected, "{}", s);
+        assert!(lit.validate().is_ok(), "{}", s);
+    }
+    for &(s, expected) in &[(r#"b'\"'"#, b'"'), (r#"b'\''"#, b'\'')] {
+        let lit = lit_byte(s);
+        assert_eq!(lit.value(), expected, "{}", s);
+        assert!(lit.validate().is_ok(), "{}", s);
+    }
+    let lit = lit_str(r#""\'\"""#);
+    assert_eq!(lit.value(), "'\"");
+    assert!(lit.validate().is_ok());
+    let lit = lit_byte_str(r#"b"\'\"""#);
+    assert_eq!(lit.value(), b"'\"".to_vec());
+    assert_eq!(lit.bytes().collect::<Vec<u8>>(), b"'\"".to_vec());
+    assert!(lit.validate().is_ok());
+}
+
+#[test]
+fn escape_count() {
+    fn count(s: &str) -> usize {
+        match lit(s) {
+            Lit::Str(lit) => lit.escape_count(),
+            Lit::ByteStr(lit) => lit.escape_count(),
+            wrong => panic!("{:?}", wrong),
+        }
+    }
+
+    assert_eq!(count(r#""a\nb\nc\n""#), 3);
+    assert_eq!(count(r#""\\\"\u{1F600}\x41""#), 4);
+    assert_eq!(count("\"a\\\n    b\""), 1);
+    assert_eq!(count(r#""plain""#), 0);
+    assert_eq!(count(r#"r"a\nb\n""#), 0);
+    assert_eq!(count(r##"r#"\"#"##), 0);
+    assert_eq!(count(r#"b"\x00\\\n""#), 3);
+    assert_eq!(count(r#"br"\x00""#), 0);
+}
+
+#[test]
+fn str_is_valid_rust_ident() {
+    fn is_valid(s: &str) -> bool {
+        lit_str(s).is_valid_rust_ident()
+    }
+
+    assert!(is_valid(r#""foo_bar""#));
+    assert!(is_valid(r#""_private""#));
+    assert!(is_valid(r#""Ünïcödé""#));
+    assert!(is_valid(r#""\x66oo""#));
+    assert!(is_valid(r#"r"self_""#));
+    assert!(!is_valid(r#""1abc""#));
+    assert!(!is_valid(r#""fn""#));
+    assert!(!is_valid(r#""Self""#));
+    assert!(!is_valid(r#""yield""#));
+    assert!(!is_valid(r#""_""#));
+    assert!(!is_valid(r#""""#));
+    assert!(!is_valid(r#""foo-bar""#));
+    assert!(!is_valid(r#""foo bar""#));
+    assert!(!is_valid(r#""r#foo""#));
+}
+
+#[cfg(feature = "lit-encoding")]
+#[test]
+fn encode_decode() {
+    let lits = vec![
+        lit("\"a\\tb\""),
+        lit("r#\"raw \"quoted\"\"#"),
+        lit("b\"\\xff\""),
+        lit("b'\\n'"),
+        lit("'\\u{1F600}'"),
+        lit("0xFF_u8"),
+        lit("1.5e10_f32"),
+        lit("340282366920938463463374607431768211455u128"),
+        syn::parse_str::<Lit>("true").unwrap(),
+        syn::parse_str::<Lit>("false").unwrap(),
+    ];
+
+    let mut encoded = Vec::new();
+    for lit in &lits {
+        lit.encode(&mut encoded);
+    }
+    let mut bytes = &encoded[..];
+    for lit in &lits {
+        assert_eq!(Lit::decode(&mut bytes).unwrap(), *lit);
+    }
+    assert!(bytes.is_empty());
+
+    let mut one = Vec::new();
+    lit("1u8").encode(&mut one);
+    assert_eq!(one, b"\x04\x03\x00\x00\x001u8");
+
+    fn decode_error(encoded: &[u8]) -> String {
+        let mut bytes = encoded;
+        let err = Lit::decode(&mut bytes).unwrap_err().to_string();
+        assert_eq!(bytes, encoded);
+        err
+    }
+    let eof = "unexpected end of input while decoding literal";
+    assert_eq!(decode_error(b""), eof);
+    assert_eq!(decode_error(b"\x04\x03\x00\x00"), eof);
+    assert_eq!(decode_error(b"\x04\x03\x00\x00\x001u"), eof);
+    assert_eq!(decode_error(b"\x09\x01\x00\x00\x001"), "unknown literal kind 9");
+    assert_eq!(decode_error(b"\x00\x01\x00\x00\x001"), "literal `1` is not of the encoded kind");
+    assert_eq!(decode_error(b"\x04\x03\x00\x00\x001 2"), "invalid literal token `1 2`");
+    assert_eq!(decode_error(b"\x00\x05\x00\x00\x00/// a"), "invalid literal token `/// a`");
+    assert_eq!(decode_error(b"\x00\x01\x00\x00\x00\xff"), "encoded literal is not valid UTF-8");
+}
+
+#[test]
+fn byte_from_escape_str() {
+    fn from_escape_str(s: &str) -> Result<u8, String> {
+        syn::LitByte::from_escape_str(s, Span::def_site())
+            .map(|lit| lit.value())
+            .map_err(|err| err.to_string())
+    }
+
+    assert_eq!(from_escape_str("\\n"), Ok(b'\n'));
+    assert_eq!(from_escape_str("\\x41"), Ok(b'A'));
+    assert_eq!(from_escape_str("\\xff"), Ok(0xff));
+    assert_eq!(from_escape_str("\\0"), Ok(0));
+    assert_eq!(from_escape_str("\\'"), Ok(b'\''));
+    assert_eq!(from_escape_str("a"), Ok(b'a'));
+    assert_eq!(from_escape_str(""), Err("empty byte escape".to_owned()));
+    assert_eq!(from_escape_str("'"), Err("unescaped ' in byte escape; use \\'".to_owned()));
+    assert_eq!(
+        from_escape_str("\\q"),
+        Err("unexpected byte 113 after \\ character in byte literal".to_owned())
+    );
+    assert_eq!(from_escape_str("ab"), Err("Expected end of byte literal".to_owned()));
+    assert_eq!(from_escape_str("é"), Err("Expected end of byte literal".to_owned()));
+
+    let lit = syn::LitByte::from_escape_str("\\n", Span::def_site()).unwrap();
+    assert_eq!(TokenStream::from(Lit::Byte(lit)).to_string(), "b'\\n'");
+}
+
+#[test]
+fn fingerprint() {
+    // The fingerprint must not change between runs or Rust releases, so pin
+    // a couple of values.
+    assert_eq!(lit("\"a\"").fingerprint(), 0x78f3_fae8_b398_b03d);
+    assert_eq!(lit("1u8").fingerprint(), 0x83a8_c998_3990_b3f7);
+
+    assert_eq!(lit("\"a\"").fingerprint(), lit("r#\"a\"#").fingerprint());
+    assert_eq!(lit("\"a\"").fingerprint(), lit("\"\\x61\"").fingerprint());
+    assert_eq!(lit("0xff_u8").fingerprint(), lit("255u8").fingerprint());
+    assert_ne!(lit("1u8").fingerprint(), lit("1u16").fingerprint());
+    assert_ne!(lit("1u8").fingerprint(), lit("1").fingerprint());
+    assert_ne!(lit("1").fingerprint(), lit("1.0").fingerprint());
+    assert_ne!(lit("\"a\"").fingerprint(), lit("b\"a\"").fingerprint());
+    assert_ne!(lit("\"a\"").fingerprint(), lit("'a'").fingerprint());
+}
+
+#[test]
+fn parse_maybe_plus() {
+    let parse = |s: &str| Lit::parse_maybe_plus.parse_str(s);
+
+    assert_eq!(parse("+5").unwrap(), lit("5"));
+    assert_eq!(parse("+ 0x10u8").unwrap(), lit("0x10u8"));
+    assert_eq!(parse("+2.5").unwrap(), lit("2.5"));
+    assert_eq!(
+        parse("+340282366920938463463374607431768211455").unwrap(),
+        lit("340282366920938463463374607431768211455")
+    );
+    assert_eq!(parse("5").unwrap(), lit("5"));
+    assert_eq!(parse("\"s\"").unwrap(), lit("\"s\""));
+
+    for s in &["+\"s\"", "+'c'", "+true", "+", "+-5"] {
+        let err = parse(s).unwrap_err();
+        assert_eq!(err.to_string(), "expected number after `+`", "{}", s);
+    }
+}
+
+#[test]
+fn float_classify() {
+    use std::num::FpCategory;
+
+    fn classify(s: &str) -> FpCategory {
+        lit_float(s).classify()
+    }
+
+    assert_eq!(classify("1.5"), FpCategory::Normal);
+    assert_eq!(classify("0.0"), FpCategory::Zero);
+    assert_eq!(classify("0e10f32"), FpCategory::Zero);
+    assert_eq!(classify("1e-310"), FpCategory::Subnormal);
+    assert_eq!(classify("2.2250738585072014e-308"), FpCategory::Normal);
+    assert_eq!(classify("1e400"), FpCategory::Infinite);
+}